@@ -38,7 +38,7 @@
 //! ```
 
 use anyhow::{Context, Result};
-use hd44780_ntb::{DisplayMode, EntryMode, FunctionMode, GpioDriver, ShiftMode, HD44780};
+use hd44780_ntb::{DisplayMode, EntryMode, FunctionMode, Geometry, GpioDriver, ShiftMode, HD44780};
 use linux_embedded_hal::sysfs_gpio::Direction;
 use linux_embedded_hal::{Delay, Pin};
 use std::io::Write;
@@ -110,7 +110,7 @@ fn display_loop(lcd: &mut GpioDriver<Pin, Pin, Pin, Delay>) -> Result<()> {
         lcd.write(message.as_bytes())
             .context("Failed to write string")?;
         // Move to second line.
-        lcd.set_dd_ram_addr(0x40)
+        lcd.set_cursor(&Geometry::LCD_16X2, 0, 1)
             .context("Failed to move to second line")?;
         // Write the second line.
         message = "... be with you!";
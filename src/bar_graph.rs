@@ -0,0 +1,221 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Smooth horizontal bar graphs and progress bars using custom characters.
+
+use crate::error::HdError::OffScreen;
+use crate::{CustomChar, Lcd, Result, HD44780};
+use std::io::Write;
+
+/// Columns of pixels in each character cell.
+const CELL_PIXELS: u32 = 5;
+/// Width of the percentage label including the `%`.
+const LABEL_WIDTH: u8 = 4;
+
+/// A horizontal bar that fills with 1/5 of a character cell resolution.
+///
+/// ## Remarks
+///
+/// The bar uses 5 custom characters, one for each of the partly to fully
+/// filled cells, which [load()] puts into 5 CG RAM slots starting at
+/// [set_first_slot()] (slot 0 by default).
+///
+/// Only the cells that changed since the last [update()] are sent to the
+/// display so it can be animated quickly even on slow drivers.
+/// Use [invalidate()] after anything else has been drawn over the bar, like
+/// when the display is cleared.
+///
+/// The widget's `width` includes the `[` and `]` end caps and the `100%` style
+/// label when they are turned on.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let mut bar = BarGraph::new(0, 1, 16);
+/// bar.set_caps(true);
+/// bar.set_label(true);
+/// bar.load(&mut lcd)?;
+/// bar.update(&mut lcd, 42, 100)?;
+/// ```
+///
+/// [load()]: #method.load
+/// [set_first_slot()]: #method.set_first_slot
+/// [update()]: #method.update
+/// [invalidate()]: #method.invalidate
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BarGraph {
+    col: u8,
+    row: u8,
+    width: u8,
+    caps: bool,
+    label: bool,
+    first_slot: u8,
+    /// Character codes last sent to the display, empty if unknown.
+    drawn: Vec<u8>,
+}
+
+impl BarGraph {
+    /// Create a new bar graph.
+    ///
+    /// ## Arguments
+    /// * `col` - Column of the left side of the widget.
+    /// * `row` - Row of the widget.
+    /// * `width` - Number of columns used by the whole widget.
+    pub fn new(col: u8, row: u8, width: u8) -> Self {
+        BarGraph {
+            col,
+            row,
+            width,
+            caps: false,
+            label: false,
+            first_slot: 0,
+            drawn: Vec::new(),
+        }
+    }
+    /// Turn `[` and `]` end caps around the bar on or off.
+    pub fn set_caps(&mut self, caps: bool) {
+        self.caps = caps;
+        self.invalidate();
+    }
+    /// Turn the percentage label after the bar on or off.
+    pub fn set_label(&mut self, label: bool) {
+        self.label = label;
+        self.invalidate();
+    }
+    /// Change the first of the 5 CG RAM slots used by the bar.
+    ///
+    /// Must be called before [load()].
+    ///
+    /// [load()]: #method.load
+    ///
+    pub fn set_first_slot(&mut self, slot: u8) {
+        self.first_slot = slot;
+        self.invalidate();
+    }
+    /// Number of columns used by the bar itself.
+    pub fn bar_width(&self) -> u8 {
+        let mut used = 0;
+        if self.caps {
+            used += 2;
+        }
+        if self.label {
+            used += LABEL_WIDTH;
+        }
+        self.width.saturating_sub(used)
+    }
+    /// Forget what is on the display so the next update redraws every cell.
+    pub fn invalidate(&mut self) {
+        self.drawn.clear();
+    }
+    /// Load the custom characters needed by the bar.
+    ///
+    /// ## Errors
+    /// Returns [InvalidCgRamSlot] if the 5 slots do not fit in CG RAM.
+    ///
+    /// [InvalidCgRamSlot]: enum.HdError.html#variant.InvalidCgRamSlot
+    ///
+    pub fn load<D>(&self, lcd: &mut Lcd<D>) -> Result
+    where
+        D: HD44780,
+    {
+        for fill in 1..=CELL_PIXELS {
+            // Left pixels filled with the bottom row left for the cursor.
+            let pixels = 0x1F & !(0x1F >> fill) as u8;
+            let mut rows = [pixels; 8];
+            rows[7] = 0;
+            let slot = self.first_slot.saturating_add(fill as u8 - 1);
+            lcd.load_custom_char(slot, &CustomChar::new(rows))?;
+        }
+        Ok(())
+    }
+    /// Show `value` out of `max` on the bar.
+    ///
+    /// Values above `max` show a full bar.
+    ///
+    /// ## Arguments
+    /// * `lcd` - Display to draw on.
+    /// * `value` - Current level.
+    /// * `max` - Level of a full bar.
+    ///
+    /// ## Errors
+    /// Returns [OffScreen] if the widget does not fit on the display.
+    ///
+    /// [OffScreen]: enum.HdError.html#variant.OffScreen
+    ///
+    pub fn update<D>(&mut self, lcd: &mut Lcd<D>, value: u32, max: u32) -> Result
+    where
+        D: HD44780,
+    {
+        let geometry = *lcd.geometry();
+        let end = self.col as u16 + self.width as u16;
+        if end > geometry.columns as u16 || self.row >= geometry.rows {
+            return Err(OffScreen((end as u8).wrapping_sub(1), self.row));
+        }
+        let cells = self.cells(value, max);
+        if self.drawn.len() != cells.len() {
+            self.drawn = vec![!0; cells.len()];
+        }
+        // Send each run of changed cells with a single cursor move.
+        let mut start = 0;
+        while start < cells.len() {
+            if cells[start] == self.drawn[start] {
+                start += 1;
+                continue;
+            }
+            let mut stop = start + 1;
+            while stop < cells.len() && cells[stop] != self.drawn[stop] {
+                stop += 1;
+            }
+            lcd.set_cursor(self.col + start as u8, self.row)?;
+            lcd.write_all(&cells[start..stop])?;
+            start = stop;
+        }
+        self.drawn = cells;
+        Ok(())
+    }
+    /// Character codes for the whole widget.
+    fn cells(&self, value: u32, max: u32) -> Vec<u8> {
+        let value = value.min(max) as u64;
+        let max = max.max(1) as u64;
+        let bar_width = self.bar_width() as u64;
+        let filled = value * bar_width * CELL_PIXELS as u64 / max;
+        let mut cells = Vec::with_capacity(self.width as usize);
+        if self.caps {
+            cells.push(b'[');
+        }
+        for cell in 0..bar_width {
+            let fill = filled.saturating_sub(cell * CELL_PIXELS as u64);
+            cells.push(match fill.min(CELL_PIXELS as u64) {
+                0 => b' ',
+                fill => self.first_slot + fill as u8 - 1,
+            });
+        }
+        if self.caps {
+            cells.push(b']');
+        }
+        if self.label {
+            let label = format!("{:>3}%", value * 100 / max);
+            cells.extend_from_slice(label.as_bytes());
+        }
+        cells.truncate(self.width as usize);
+        cells
+    }
+}
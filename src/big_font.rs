@@ -0,0 +1,276 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Draws large text across several rows using custom characters.
+
+use crate::error::HdError::OffScreen;
+use crate::{CustomChar, Lcd, Result, HD44780};
+use std::io::Write;
+
+// CG RAM slots of the building blocks.
+const FULL: u8 = 0;
+const UPPER: u8 = 1;
+const LOWER: u8 = 2;
+const UPPER_MIDDLE: u8 = 3;
+const LOWER_MIDDLE: u8 = 4;
+const BLANK: u8 = b' ';
+
+/// Custom characters used to build the large glyphs in slots 0 to 4.
+const BLOCKS: [[u8; 8]; 5] = [
+    [0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F, 0x1F],
+    [0x1F, 0x1F, 0x1F, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x1F, 0x1F],
+    [0x1F, 0x1F, 0x1F, 0x00, 0x00, 0x00, 0x1F, 0x1F],
+    [0x1F, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x1F, 0x1F],
+];
+
+/// Glyphs as 5 rows of up to 3 pixels with bit 2 being the left pixel.
+const GLYPHS: [(char, u8, [u8; 5]); 40] = [
+    ('0', 3, [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', 3, [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', 3, [0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', 3, [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', 3, [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', 3, [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', 3, [0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', 3, [0b111, 0b001, 0b001, 0b001, 0b001]),
+    ('8', 3, [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', 3, [0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('A', 3, [0b111, 0b101, 0b111, 0b101, 0b101]),
+    ('B', 3, [0b110, 0b101, 0b110, 0b101, 0b110]),
+    ('C', 3, [0b111, 0b100, 0b100, 0b100, 0b111]),
+    ('D', 3, [0b110, 0b101, 0b101, 0b101, 0b110]),
+    ('E', 3, [0b111, 0b100, 0b110, 0b100, 0b111]),
+    ('F', 3, [0b111, 0b100, 0b110, 0b100, 0b100]),
+    ('G', 3, [0b111, 0b100, 0b101, 0b101, 0b111]),
+    ('H', 3, [0b101, 0b101, 0b111, 0b101, 0b101]),
+    ('I', 3, [0b111, 0b010, 0b010, 0b010, 0b111]),
+    ('J', 3, [0b001, 0b001, 0b001, 0b101, 0b111]),
+    ('K', 3, [0b101, 0b101, 0b110, 0b101, 0b101]),
+    ('L', 3, [0b100, 0b100, 0b100, 0b100, 0b111]),
+    ('M', 3, [0b101, 0b111, 0b111, 0b101, 0b101]),
+    ('N', 3, [0b110, 0b101, 0b101, 0b101, 0b101]),
+    ('O', 3, [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('P', 3, [0b111, 0b101, 0b111, 0b100, 0b100]),
+    ('Q', 3, [0b111, 0b101, 0b101, 0b111, 0b001]),
+    ('R', 3, [0b110, 0b101, 0b110, 0b101, 0b101]),
+    ('S', 3, [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('T', 3, [0b111, 0b010, 0b010, 0b010, 0b010]),
+    ('U', 3, [0b101, 0b101, 0b101, 0b101, 0b111]),
+    ('V', 3, [0b101, 0b101, 0b101, 0b101, 0b010]),
+    ('W', 3, [0b101, 0b101, 0b111, 0b111, 0b101]),
+    ('X', 3, [0b101, 0b101, 0b010, 0b101, 0b101]),
+    ('Y', 3, [0b101, 0b101, 0b010, 0b010, 0b010]),
+    ('Z', 3, [0b111, 0b001, 0b010, 0b100, 0b111]),
+    ('-', 3, [0b000, 0b000, 0b111, 0b000, 0b000]),
+    (':', 1, [0b000, 0b100, 0b000, 0b100, 0b000]),
+    ('.', 1, [0b000, 0b000, 0b000, 0b000, 0b100]),
+    (' ', 2, [0b000, 0b000, 0b000, 0b000, 0b000]),
+];
+
+/// How many rows the large characters use.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BigFontHeight {
+    /// For 2 row displays like 16x2 and 20x2.
+    TwoRows,
+    /// For 4 row displays leaving a row for other information.
+    ThreeRows,
+    /// Uses all of a 4 row display like 20x4.
+    FourRows,
+}
+
+impl BigFontHeight {
+    /// Number of display rows used.
+    pub fn rows(self) -> u8 {
+        match self {
+            BigFontHeight::TwoRows => 2,
+            BigFontHeight::ThreeRows => 3,
+            BigFontHeight::FourRows => 4,
+        }
+    }
+}
+
+/// Large digits and uppercase letters made from custom characters.
+///
+/// ## Remarks
+///
+/// All of the large characters are built from 5 block shaped custom characters
+/// which [load()] puts into CG RAM slots 0 to 4 so only slots 5 to 7 are left
+/// for anything else.
+///
+/// Digits, `A`-`Z` (lowercase is drawn as uppercase), `-`, `:`, `.`, and space
+/// are supported with most characters 3 columns wide and a blank column
+/// between characters.
+/// Anything else is drawn as a space.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let font = BigFont::new(BigFontHeight::TwoRows);
+/// font.load(&mut lcd)?;
+/// font.draw_big_text(&mut lcd, 0, 0, "12:34")?;
+/// ```
+///
+/// [load()]: #method.load
+///
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BigFont {
+    height: BigFontHeight,
+}
+
+impl BigFont {
+    /// Create a new big font.
+    ///
+    /// ## Arguments
+    /// * `height` - How many rows the characters use.
+    pub fn new(height: BigFontHeight) -> Self {
+        BigFont { height }
+    }
+    /// How many rows the characters use.
+    pub fn height(&self) -> BigFontHeight {
+        self.height
+    }
+    /// Load the custom characters needed by the font.
+    pub fn load<D>(&self, lcd: &mut Lcd<D>) -> Result
+    where
+        D: HD44780,
+    {
+        for (slot, rows) in BLOCKS.iter().enumerate() {
+            lcd.load_custom_char(slot as u8, &CustomChar::new(*rows))?;
+        }
+        Ok(())
+    }
+    /// Number of columns `text` would use.
+    pub fn text_width(&self, text: &str) -> u8 {
+        let width: usize = text.chars().map(|c| glyph(c).1 as usize + 1).sum();
+        width.saturating_sub(1).min(u8::MAX as usize) as u8
+    }
+    /// Draw large text with its top left corner at `col`, `row`.
+    ///
+    /// Text that goes past the right side of the display is cut off.
+    ///
+    /// ## Arguments
+    /// * `lcd` - Display to draw on.
+    /// * `col` - Column of the left side of the text.
+    /// * `row` - Top row of the text.
+    ///
+    /// ## Errors
+    /// Returns [OffScreen] if the text would start past the right side or go
+    /// below the bottom of the display.
+    ///
+    /// [OffScreen]: enum.HdError.html#variant.OffScreen
+    ///
+    pub fn draw_big_text<D>(&self, lcd: &mut Lcd<D>, col: u8, row: u8, text: &str) -> Result
+    where
+        D: HD44780,
+    {
+        let geometry = *lcd.geometry();
+        let rows = self.height.rows();
+        if col >= geometry.columns || row as u16 + rows as u16 > geometry.rows as u16 {
+            return Err(OffScreen(col, row.saturating_add(rows - 1)));
+        }
+        let cells = self.cells(text);
+        let width = (geometry.columns - col) as usize;
+        for (offset, line) in cells.iter().enumerate() {
+            lcd.set_cursor(col, row + offset as u8)?;
+            lcd.write_all(&line[..line.len().min(width)])?;
+        }
+        Ok(())
+    }
+    /// Character codes for each row of the text.
+    fn cells(&self, text: &str) -> Vec<Vec<u8>> {
+        let mut lines = vec![Vec::new(); self.height.rows() as usize];
+        for (i, c) in text.chars().enumerate() {
+            let (_, width, pixels) = glyph(c);
+            for x in 0..width {
+                let column: Vec<bool> = pixels.iter().map(|row| row & (0b100 >> x) != 0).collect();
+                for (line, code) in lines.iter_mut().zip(self.column_cells(&column)) {
+                    if i > 0 && x == 0 {
+                        line.push(BLANK);
+                    }
+                    line.push(code);
+                }
+            }
+        }
+        lines
+    }
+    /// Turn one column of 5 pixels into display cells.
+    fn column_cells(&self, p: &[bool]) -> Vec<u8> {
+        match self.height {
+            // Middle pixel is shared by both cells as bars across the middle.
+            BigFontHeight::TwoRows => {
+                let top = if p[1] {
+                    if p[0] || p[2] {
+                        FULL
+                    } else {
+                        LOWER
+                    }
+                } else {
+                    match (p[0], p[2]) {
+                        (true, true) => UPPER_MIDDLE,
+                        (true, false) => UPPER,
+                        (false, true) => LOWER,
+                        (false, false) => BLANK,
+                    }
+                };
+                let bottom = if p[3] {
+                    if p[2] || p[4] {
+                        FULL
+                    } else {
+                        LOWER
+                    }
+                } else {
+                    match (p[2], p[4]) {
+                        (true, true) => LOWER_MIDDLE,
+                        (true, false) => UPPER,
+                        (false, true) => LOWER,
+                        (false, false) => BLANK,
+                    }
+                };
+                vec![top, bottom]
+            }
+            // Each cell is 2 pixels high.
+            BigFontHeight::ThreeRows => halves(&[p[0], p[1], p[2], p[3], p[4], false]),
+            // Stretch the bowls of the characters to 7 pixels.
+            BigFontHeight::FourRows => halves(&[p[0], p[1], p[1], p[2], p[3], p[3], p[4], false]),
+        }
+    }
+}
+
+fn halves(pixels: &[bool]) -> Vec<u8> {
+    pixels
+        .chunks(2)
+        .map(|half| match (half[0], half[1]) {
+            (true, true) => FULL,
+            (true, false) => UPPER,
+            (false, true) => LOWER,
+            (false, false) => BLANK,
+        })
+        .collect()
+}
+
+fn glyph(c: char) -> (char, u8, [u8; 5]) {
+    let c = c.to_ascii_uppercase();
+    GLYPHS
+        .iter()
+        .find(|(g, _, _)| *g == c)
+        .copied()
+        .unwrap_or(GLYPHS[GLYPHS.len() - 1])
+}
@@ -0,0 +1,197 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Shares the few CG RAM slots between any number of custom characters.
+
+use crate::error::HdError::CgRamFull;
+use crate::{CustomChar, Lcd, Result, HD44780};
+
+/// A custom character loaded into one of the CG RAM slots.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Slot {
+    char: CustomChar,
+    /// Tick of the last time the character was asked for.
+    used: u64,
+    /// Frame the character was last asked for in.
+    frame: u64,
+}
+
+/// Hands out CG RAM slots to custom characters as they are needed.
+///
+/// ## Remarks
+///
+/// Characters are looked up by their bitmap so asking for one that is
+/// already loaded costs nothing.
+/// When a new character is needed and all slots are full the least recently
+/// used character that is not shown on the display is replaced.
+/// A character still on the display is never replaced as that would change
+/// what is already shown, so the old characters must be drawn over before new
+/// ones can take their slots.
+///
+/// A frame is everything drawn between calls to [begin_frame()].
+/// Asking for more different characters than there are slots, 8 with the 5x8
+/// font or 4 with the 5x10 font, in one frame or on the display at once is an
+/// error.
+///
+/// The manager assumes it owns all of CG RAM so [reset()] must be used if
+/// anything else loads custom characters.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let mut cg_ram = CgRamManager::new();
+/// cg_ram.begin_frame();
+/// let code = cg_ram.glyph(&mut lcd, &heart)?;
+/// lcd.write_all(&[code])?;
+/// ```
+///
+/// [begin_frame()]: #method.begin_frame
+/// [reset()]: #method.reset
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CgRamManager {
+    slots: Vec<Option<Slot>>,
+    tick: u64,
+    frame: u64,
+}
+
+impl CgRamManager {
+    /// Create a new manager with all slots free.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Start a new frame.
+    ///
+    /// Characters asked for in earlier frames can be replaced once they are no
+    /// longer on the display.
+    pub fn begin_frame(&mut self) {
+        self.frame += 1;
+    }
+    /// Forget what is in CG RAM so every character is loaded again when asked for.
+    pub fn reset(&mut self) {
+        self.slots.clear();
+    }
+    /// Character code of an already loaded custom character if any.
+    pub fn code_of(&self, char: &CustomChar) -> Option<u8> {
+        let tall = self.slots.len() == 4;
+        self.position(char).map(|slot| code(slot as u8, tall))
+    }
+    /// Get the character code to write for a custom character, loading it into
+    /// CG RAM first if needed.
+    ///
+    /// ## Arguments
+    /// * `lcd` - Display the character will be shown on.
+    /// * `char` - The bitmap wanted.
+    ///
+    /// ## Errors
+    /// Returns [CgRamFull] if all of the slots are used by the current frame or
+    /// by characters still on the display and any errors from [Lcd::load_custom_char()].
+    ///
+    /// [CgRamFull]: enum.HdError.html#variant.CgRamFull
+    /// [Lcd::load_custom_char()]: struct.Lcd.html#method.load_custom_char
+    ///
+    pub fn glyph<D>(&mut self, lcd: &mut Lcd<D>, char: &CustomChar) -> Result<u8>
+    where
+        D: HD44780,
+    {
+        let tall = lcd.state().is_5x10();
+        let count = if tall { 4 } else { 8 };
+        if self.slots.len() != count {
+            // Font changed so the old slots mean nothing.
+            self.slots = vec![None; count];
+        }
+        self.tick += 1;
+        let (tick, frame) = (self.tick, self.frame);
+        if let Some(i) = self.position(char) {
+            if let Some(slot) = &mut self.slots[i] {
+                slot.used = tick;
+                slot.frame = frame;
+            }
+            return Ok(code(i as u8, tall));
+        }
+        let shown = shown_codes(lcd, tall);
+        let slot = match self.slots.iter().position(Option::is_none) {
+            Some(free) => free,
+            None => self
+                .victim(|s, code| s.frame != frame && !shown[code as usize])
+                .ok_or(CgRamFull(count as u8))?,
+        };
+        lcd.load_custom_char(slot as u8, char)?;
+        self.slots[slot] = Some(Slot {
+            char: *char,
+            used: tick,
+            frame,
+        });
+        Ok(code(slot as u8, tall))
+    }
+    fn position(&self, char: &CustomChar) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|s| matches!(s, Some(s) if s.char == *char))
+    }
+    /// Least recently used slot that can be replaced.
+    fn victim<F>(&self, can_replace: F) -> Option<usize>
+    where
+        F: Fn(&Slot, u8) -> bool,
+    {
+        let tall = self.slots.len() == 4;
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.map(|s| (i, s)))
+            .filter(|(i, s)| can_replace(s, code(*i as u8, tall)))
+            .min_by_key(|(_, s)| s.used)
+            .map(|(i, _)| i)
+    }
+}
+
+/// Character code which shows a slot.
+///
+/// With the 5x10 font the lowest bit of the code is ignored.
+pub(crate) fn code(slot: u8, tall: bool) -> u8 {
+    if tall {
+        slot << 1
+    } else {
+        slot
+    }
+}
+
+/// Which of the CG RAM character codes are in the visible part of DD RAM.
+///
+/// Codes 8 to 15 show the same characters as 0 to 7.
+fn shown_codes<D>(lcd: &Lcd<D>, tall: bool) -> [bool; 8]
+where
+    D: HD44780,
+{
+    let state = lcd.state();
+    let mut shown = [false; 8];
+    for row in 0..state.rows() {
+        for col in 0..state.columns() {
+            if let Some(address) = state.visible_address(col, row) {
+                let byte = state.dd_ram(address);
+                if byte < 0x10 {
+                    let byte = if tall { byte & 0x06 } else { byte & 0x07 };
+                    shown[byte as usize] = true;
+                }
+            }
+        }
+    }
+    shown
+}
@@ -0,0 +1,591 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Converts Unicode text into the character codes of the HD44780 character
+//! ROMs.
+
+use crate::error::HdError::UnmappableChar;
+use crate::{CharRom, Result};
+
+/// What to do with characters that are not in the character ROM.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Fallback {
+    /// Use the closest looking characters instead (`ł` → `l`, `€` → `EUR`)
+    /// or `?` if there are none.
+    #[default]
+    Transliterate,
+    /// Use the given character code instead.
+    Replace(u8),
+    /// Return an [UnmappableChar] error.
+    ///
+    /// [UnmappableChar]: enum.HdError.html#variant.UnmappableChar
+    ///
+    Error,
+}
+
+/// Encoder from Unicode text to character codes for one of the character ROMs.
+///
+/// ## Remarks
+///
+/// Besides the characters that are actually in the ROM, full-width ASCII forms
+/// are converted to their normal forms and on A00 hiragana and full-width
+/// katakana are converted to the half-width katakana in the ROM with voiced
+/// marks as separate characters (`ガ` → `ｶﾞ`).
+/// A00 does not have a degree sign so the look-alike semi-voiced mark (`ﾟ`) is
+/// used for `°`.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let charset = Charset::new(CharRom::A02, Fallback::Transliterate);
+/// lcd.write_str("Müller 25°C", &charset)?;
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Charset {
+    rom: CharRom,
+    fallback: Fallback,
+}
+
+impl Charset {
+    /// Create a new encoder.
+    ///
+    /// ## Arguments
+    /// * `rom` - Which character ROM the display has.
+    /// * `fallback` - What to do with characters that can not be shown.
+    pub fn new(rom: CharRom, fallback: Fallback) -> Self {
+        Charset { rom, fallback }
+    }
+    /// Character ROM being encoded for.
+    pub fn rom(&self) -> CharRom {
+        self.rom
+    }
+    /// Encode all of `text`.
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(text.len());
+        for c in text.chars() {
+            self.encode_char(c, &mut bytes)?;
+        }
+        Ok(bytes)
+    }
+    /// Encode a single character adding its character code(s) to `bytes`.
+    ///
+    /// Most characters are a single code but katakana with voiced marks and
+    /// transliterations can be more.
+    pub fn encode_char(&self, c: char, bytes: &mut Vec<u8>) -> Result {
+        if self.map(c, bytes) {
+            return Ok(());
+        }
+        match self.fallback {
+            Fallback::Transliterate => {
+                match transliteration(c) {
+                    Some(text) => text.chars().for_each(|t| {
+                        if !self.map(t, bytes) {
+                            bytes.push(b'?');
+                        }
+                    }),
+                    None => bytes.push(b'?'),
+                }
+                Ok(())
+            }
+            Fallback::Replace(code) => {
+                bytes.push(code);
+                Ok(())
+            }
+            Fallback::Error => Err(UnmappableChar(c)),
+        }
+    }
+    fn map(&self, c: char, bytes: &mut Vec<u8>) -> bool {
+        // Full-width ASCII forms.
+        let c = match c {
+            '\u{FF01}'..='\u{FF5E}' => char::from((c as u32 - 0xFEE0) as u8),
+            '\u{3000}' => ' ',
+            _ => c,
+        };
+        let code = match self.rom {
+            CharRom::A00 => match a00_code(c) {
+                Some(code) => Some(code),
+                None => {
+                    if let Some(codes) = a00_kana(c) {
+                        bytes.extend_from_slice(codes);
+                        return true;
+                    }
+                    None
+                }
+            },
+            CharRom::A02 => a02_code(c),
+        };
+        match code {
+            Some(code) => {
+                bytes.push(code);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn a00_code(c: char) -> Option<u8> {
+    let code = match c {
+        ' '..='[' | ']'..='}' => c as u8,
+        '¥' => 0x5C,
+        '→' => 0x7E,
+        '←' => 0x7F,
+        // Half-width katakana are in the same order as the ROM.
+        '\u{FF61}'..='\u{FF9F}' => (c as u32 - 0xFF61 + 0xA1) as u8,
+        '°' => 0xDF,
+        'α' => 0xE0,
+        'ä' => 0xE1,
+        'β' => 0xE2,
+        'ε' => 0xE3,
+        '\u{3BC}' | '\u{B5}' => 0xE4,
+        'σ' => 0xE5,
+        'ρ' => 0xE6,
+        '√' => 0xE8,
+        '¢' => 0xEC,
+        '£' => 0xED,
+        'ñ' => 0xEE,
+        'ö' => 0xEF,
+        'θ' => 0xF2,
+        '∞' => 0xF3,
+        '\u{3A9}' | '\u{2126}' => 0xF4,
+        'ü' => 0xF5,
+        'Σ' => 0xF6,
+        'π' => 0xF7,
+        '千' => 0xFA,
+        '万' => 0xFB,
+        '円' => 0xFC,
+        '÷' => 0xFD,
+        '█' => 0xFF,
+        _ => return None,
+    };
+    Some(code)
+}
+
+fn a00_kana(c: char) -> Option<&'static [u8]> {
+    let codes: &'static [u8] = match c {
+        // Hiragana are in the same order as katakana.
+        '\u{3041}'..='\u{3096}' => KATAKANA[c as usize - 0x3041],
+        '\u{30A1}'..='\u{30F6}' => KATAKANA[c as usize - 0x30A1],
+        '。' => &[0xA1],
+        '「' => &[0xA2],
+        '」' => &[0xA3],
+        '、' => &[0xA4],
+        '・' => &[0xA5],
+        'ー' => &[0xB0],
+        '゛' => &[0xDE],
+        '゜' => &[0xDF],
+        _ => return None,
+    };
+    Some(codes)
+}
+
+fn a02_code(c: char) -> Option<u8> {
+    let code = match c {
+        ' '..='~' | '\u{A0}'..='\u{FF}' => c as u32 as u8,
+        '⌂' => 0x7F,
+        '▶' | '►' => 0x10,
+        '◀' | '◄' => 0x11,
+        '“' => 0x12,
+        '”' => 0x13,
+        '⏫' => 0x14,
+        '⏬' => 0x15,
+        '●' => 0x16,
+        '↲' | '↵' | '⏎' => 0x17,
+        '↑' => 0x18,
+        '↓' => 0x19,
+        '→' => 0x1A,
+        '←' => 0x1B,
+        '≤' => 0x1C,
+        '≥' => 0x1D,
+        '▲' => 0x1E,
+        '▼' => 0x1F,
+        'Б' => 0x80,
+        'Д' => 0x81,
+        'Ж' => 0x82,
+        'З' => 0x83,
+        'И' => 0x84,
+        'Й' => 0x85,
+        'Л' => 0x86,
+        'П' => 0x87,
+        'У' => 0x88,
+        'Ц' => 0x89,
+        'Ч' => 0x8A,
+        'Ш' => 0x8B,
+        'Щ' => 0x8C,
+        'Ъ' => 0x8D,
+        'Ы' => 0x8E,
+        'Э' => 0x8F,
+        'α' => 0x90,
+        '♪' => 0x91,
+        // Cyrillic Ge looks the same as Gamma.
+        'Γ' | 'Г' => 0x92,
+        'π' => 0x93,
+        'Σ' => 0x94,
+        'σ' => 0x95,
+        '♬' => 0x96,
+        'τ' => 0x97,
+        '🔔' => 0x98,
+        'Θ' => 0x99,
+        '\u{3A9}' | '\u{2126}' => 0x9A,
+        'δ' => 0x9B,
+        '∞' => 0x9C,
+        '♥' => 0x9D,
+        'ε' => 0x9E,
+        '∩' => 0x9F,
+        _ => return None,
+    };
+    Some(code)
+}
+
+fn transliteration(c: char) -> Option<&'static str> {
+    SYMBOLS
+        .iter()
+        .chain(LATIN.iter())
+        .find(|(from, _)| *from == c)
+        .map(|(_, to)| *to)
+}
+
+/// A00 codes for full-width katakana `ァ` (U+30A1) to `ヶ` (U+30F6).
+///
+/// Katakana without a half-width form use the closest one.
+const KATAKANA: [&[u8]; 86] = [
+    &[0xA7],       // ァ
+    &[0xB1],       // ア
+    &[0xA8],       // ィ
+    &[0xB2],       // イ
+    &[0xA9],       // ゥ
+    &[0xB3],       // ウ
+    &[0xAA],       // ェ
+    &[0xB4],       // エ
+    &[0xAB],       // ォ
+    &[0xB5],       // オ
+    &[0xB6],       // カ
+    &[0xB6, 0xDE], // ガ
+    &[0xB7],       // キ
+    &[0xB7, 0xDE], // ギ
+    &[0xB8],       // ク
+    &[0xB8, 0xDE], // グ
+    &[0xB9],       // ケ
+    &[0xB9, 0xDE], // ゲ
+    &[0xBA],       // コ
+    &[0xBA, 0xDE], // ゴ
+    &[0xBB],       // サ
+    &[0xBB, 0xDE], // ザ
+    &[0xBC],       // シ
+    &[0xBC, 0xDE], // ジ
+    &[0xBD],       // ス
+    &[0xBD, 0xDE], // ズ
+    &[0xBE],       // セ
+    &[0xBE, 0xDE], // ゼ
+    &[0xBF],       // ソ
+    &[0xBF, 0xDE], // ゾ
+    &[0xC0],       // タ
+    &[0xC0, 0xDE], // ダ
+    &[0xC1],       // チ
+    &[0xC1, 0xDE], // ヂ
+    &[0xAF],       // ッ
+    &[0xC2],       // ツ
+    &[0xC2, 0xDE], // ヅ
+    &[0xC3],       // テ
+    &[0xC3, 0xDE], // デ
+    &[0xC4],       // ト
+    &[0xC4, 0xDE], // ド
+    &[0xC5],       // ナ
+    &[0xC6],       // ニ
+    &[0xC7],       // ヌ
+    &[0xC8],       // ネ
+    &[0xC9],       // ノ
+    &[0xCA],       // ハ
+    &[0xCA, 0xDE], // バ
+    &[0xCA, 0xDF], // パ
+    &[0xCB],       // ヒ
+    &[0xCB, 0xDE], // ビ
+    &[0xCB, 0xDF], // ピ
+    &[0xCC],       // フ
+    &[0xCC, 0xDE], // ブ
+    &[0xCC, 0xDF], // プ
+    &[0xCD],       // ヘ
+    &[0xCD, 0xDE], // ベ
+    &[0xCD, 0xDF], // ペ
+    &[0xCE],       // ホ
+    &[0xCE, 0xDE], // ボ
+    &[0xCE, 0xDF], // ポ
+    &[0xCF],       // マ
+    &[0xD0],       // ミ
+    &[0xD1],       // ム
+    &[0xD2],       // メ
+    &[0xD3],       // モ
+    &[0xAC],       // ャ
+    &[0xD4],       // ヤ
+    &[0xAD],       // ュ
+    &[0xD5],       // ユ
+    &[0xAE],       // ョ
+    &[0xD6],       // ヨ
+    &[0xD7],       // ラ
+    &[0xD8],       // リ
+    &[0xD9],       // ル
+    &[0xDA],       // レ
+    &[0xDB],       // ロ
+    &[0xDC],       // ヮ
+    &[0xDC],       // ワ
+    &[0xB2],       // ヰ
+    &[0xB4],       // ヱ
+    &[0xA6],       // ヲ
+    &[0xDD],       // ン
+    &[0xB3, 0xDE], // ヴ
+    &[0xB6],       // ヵ
+    &[0xB9],       // ヶ
+];
+
+/// Look-alikes for common symbols and punctuation.
+const SYMBOLS: [(char, &str); 38] = [
+    ('\u{A0}', " "),
+    ('¡', "!"),
+    ('¦', "|"),
+    ('¨', "\""),
+    ('©', "(C)"),
+    ('ª', "a"),
+    ('«', "<<"),
+    ('¬', "-"),
+    ('®', "(R)"),
+    ('¯', "-"),
+    ('±', "+-"),
+    ('²', "2"),
+    ('³', "3"),
+    ('´', "'"),
+    ('·', "."),
+    ('¸', ","),
+    ('¹', "1"),
+    ('º', "o"),
+    ('»', ">>"),
+    ('¼', "1/4"),
+    ('½', "1/2"),
+    ('¾', "3/4"),
+    ('¿', "?"),
+    ('×', "x"),
+    ('÷', "/"),
+    ('ß', "ss"),
+    ('‘', "'"),
+    ('’', "'"),
+    ('‚', ","),
+    ('“', "\""),
+    ('”', "\""),
+    ('„', "\""),
+    ('–', "-"),
+    ('—', "-"),
+    ('•', "*"),
+    ('…', "..."),
+    ('€', "EUR"),
+    ('™', "TM"),
+];
+
+/// Latin-1 and Latin Extended-A letters without their accents.
+const LATIN: [(char, &str); 190] = [
+    ('À', "A"),
+    ('Á', "A"),
+    ('Â', "A"),
+    ('Ã', "A"),
+    ('Ä', "A"),
+    ('Å', "A"),
+    ('Æ', "AE"),
+    ('Ç', "C"),
+    ('È', "E"),
+    ('É', "E"),
+    ('Ê', "E"),
+    ('Ë', "E"),
+    ('Ì', "I"),
+    ('Í', "I"),
+    ('Î', "I"),
+    ('Ï', "I"),
+    ('Ð', "D"),
+    ('Ñ', "N"),
+    ('Ò', "O"),
+    ('Ó', "O"),
+    ('Ô', "O"),
+    ('Õ', "O"),
+    ('Ö', "O"),
+    ('Ø', "O"),
+    ('Ù', "U"),
+    ('Ú', "U"),
+    ('Û', "U"),
+    ('Ü', "U"),
+    ('Ý', "Y"),
+    ('Þ', "Th"),
+    ('ß', "ss"),
+    ('à', "a"),
+    ('á', "a"),
+    ('â', "a"),
+    ('ã', "a"),
+    ('ä', "a"),
+    ('å', "a"),
+    ('æ', "ae"),
+    ('ç', "c"),
+    ('è', "e"),
+    ('é', "e"),
+    ('ê', "e"),
+    ('ë', "e"),
+    ('ì', "i"),
+    ('í', "i"),
+    ('î', "i"),
+    ('ï', "i"),
+    ('ð', "d"),
+    ('ñ', "n"),
+    ('ò', "o"),
+    ('ó', "o"),
+    ('ô', "o"),
+    ('õ', "o"),
+    ('ö', "o"),
+    ('ø', "o"),
+    ('ù', "u"),
+    ('ú', "u"),
+    ('û', "u"),
+    ('ü', "u"),
+    ('ý', "y"),
+    ('þ', "th"),
+    ('ÿ', "y"),
+    ('Ā', "A"),
+    ('ā', "a"),
+    ('Ă', "A"),
+    ('ă', "a"),
+    ('Ą', "A"),
+    ('ą', "a"),
+    ('Ć', "C"),
+    ('ć', "c"),
+    ('Ĉ', "C"),
+    ('ĉ', "c"),
+    ('Ċ', "C"),
+    ('ċ', "c"),
+    ('Č', "C"),
+    ('č', "c"),
+    ('Ď', "D"),
+    ('ď', "d"),
+    ('Đ', "D"),
+    ('đ', "d"),
+    ('Ē', "E"),
+    ('ē', "e"),
+    ('Ĕ', "E"),
+    ('ĕ', "e"),
+    ('Ė', "E"),
+    ('ė', "e"),
+    ('Ę', "E"),
+    ('ę', "e"),
+    ('Ě', "E"),
+    ('ě', "e"),
+    ('Ĝ', "G"),
+    ('ĝ', "g"),
+    ('Ğ', "G"),
+    ('ğ', "g"),
+    ('Ġ', "G"),
+    ('ġ', "g"),
+    ('Ģ', "G"),
+    ('ģ', "g"),
+    ('Ĥ', "H"),
+    ('ĥ', "h"),
+    ('Ħ', "H"),
+    ('ħ', "h"),
+    ('Ĩ', "I"),
+    ('ĩ', "i"),
+    ('Ī', "I"),
+    ('ī', "i"),
+    ('Ĭ', "I"),
+    ('ĭ', "i"),
+    ('Į', "I"),
+    ('į', "i"),
+    ('İ', "I"),
+    ('ı', "i"),
+    ('Ĳ', "IJ"),
+    ('ĳ', "ij"),
+    ('Ĵ', "J"),
+    ('ĵ', "j"),
+    ('Ķ', "K"),
+    ('ķ', "k"),
+    ('ĸ', "k"),
+    ('Ĺ', "L"),
+    ('ĺ', "l"),
+    ('Ļ', "L"),
+    ('ļ', "l"),
+    ('Ľ', "L"),
+    ('ľ', "l"),
+    ('Ŀ', "L"),
+    ('ŀ', "l"),
+    ('Ł', "L"),
+    ('ł', "l"),
+    ('Ń', "N"),
+    ('ń', "n"),
+    ('Ņ', "N"),
+    ('ņ', "n"),
+    ('Ň', "N"),
+    ('ň', "n"),
+    ('ŉ', "n"),
+    ('Ŋ', "N"),
+    ('ŋ', "n"),
+    ('Ō', "O"),
+    ('ō', "o"),
+    ('Ŏ', "O"),
+    ('ŏ', "o"),
+    ('Ő', "O"),
+    ('ő', "o"),
+    ('Œ', "OE"),
+    ('œ', "oe"),
+    ('Ŕ', "R"),
+    ('ŕ', "r"),
+    ('Ŗ', "R"),
+    ('ŗ', "r"),
+    ('Ř', "R"),
+    ('ř', "r"),
+    ('Ś', "S"),
+    ('ś', "s"),
+    ('Ŝ', "S"),
+    ('ŝ', "s"),
+    ('Ş', "S"),
+    ('ş', "s"),
+    ('Š', "S"),
+    ('š', "s"),
+    ('Ţ', "T"),
+    ('ţ', "t"),
+    ('Ť', "T"),
+    ('ť', "t"),
+    ('Ŧ', "T"),
+    ('ŧ', "t"),
+    ('Ũ', "U"),
+    ('ũ', "u"),
+    ('Ū', "U"),
+    ('ū', "u"),
+    ('Ŭ', "U"),
+    ('ŭ', "u"),
+    ('Ů', "U"),
+    ('ů', "u"),
+    ('Ű', "U"),
+    ('ű', "u"),
+    ('Ų', "U"),
+    ('ų', "u"),
+    ('Ŵ', "W"),
+    ('ŵ', "w"),
+    ('Ŷ', "Y"),
+    ('ŷ', "y"),
+    ('Ÿ', "Y"),
+    ('Ź', "Z"),
+    ('ź', "z"),
+    ('Ż', "Z"),
+    ('ż', "z"),
+    ('Ž', "Z"),
+    ('ž', "z"),
+    ('ſ', "s"),
+];
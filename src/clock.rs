@@ -0,0 +1,139 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Clocks used to time stamp and wait out the delays of commands.
+
+use embedded_hal::blocking::delay::DelayUs;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// A source of time that can also be waited on.
+///
+/// ## Remarks
+///
+/// [SystemClock] uses the real time while [VirtualClock] only moves when it is
+/// waited on, which makes timing tests fast and always give the same results.
+/// Both also implement `DelayUs` so they can be given to any driver that
+/// needs a delay.
+///
+/// [SystemClock]: struct.SystemClock.html
+/// [VirtualClock]: struct.VirtualClock.html
+///
+pub trait Clock {
+    /// Time passed since the clock was created.
+    fn now(&self) -> Duration;
+    /// Wait for `us` microseconds to pass.
+    fn wait_us(&mut self, us: u32);
+}
+
+/// A clock using the system's monotonic time which sleeps when waited on.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Create a new clock starting from now.
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+    fn wait_us(&mut self, us: u32) {
+        std::thread::sleep(Duration::from_micros(us as u64));
+    }
+}
+
+/// A clock that only moves forward by exactly the time waited on it.
+///
+/// ## Remarks
+///
+/// Clones share the same time so one can be given to a driver as its delay
+/// while another is kept to check how much time the driver waited.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let clock = VirtualClock::new();
+/// let mut lcd = GpioDriver::new(rs, e, data, clock.clone());
+/// lcd.clear_display()?;
+/// assert!(clock.now() >= Duration::from_micros(1520));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct VirtualClock {
+    elapsed: Rc<Cell<Duration>>,
+}
+
+impl VirtualClock {
+    /// Create a new clock starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Move the clock forward without waiting.
+    pub fn advance(&self, by: Duration) {
+        self.elapsed.set(self.elapsed.get() + by);
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Duration {
+        self.elapsed.get()
+    }
+    fn wait_us(&mut self, us: u32) {
+        self.advance(Duration::from_micros(us as u64));
+    }
+}
+
+impl DelayUs<u16> for SystemClock {
+    fn delay_us(&mut self, us: u16) {
+        self.wait_us(us as u32);
+    }
+}
+
+impl DelayUs<u32> for SystemClock {
+    fn delay_us(&mut self, us: u32) {
+        self.wait_us(us);
+    }
+}
+
+impl DelayUs<u16> for VirtualClock {
+    fn delay_us(&mut self, us: u16) {
+        self.wait_us(us as u32);
+    }
+}
+
+impl DelayUs<u32> for VirtualClock {
+    fn delay_us(&mut self, us: u32) {
+        self.wait_us(us);
+    }
+}
@@ -22,8 +22,8 @@
 //! Contains the HD44780 display controller command set trait and associated
 //! parameter types.
 
-use crate::error::HdError::InvalidLineAndFontMode;
-use crate::Result;
+use crate::error::HdError::{InvalidDataBusLen, InvalidDdRamAddr, InvalidLineAndFontMode};
+use crate::{Charset, Geometry, Instruction, Result};
 use std::io::Write;
 
 /// Complete command set for HD44780 display controller.
@@ -57,7 +57,7 @@ pub trait HD44780: Write {
     /// ## Arguments
     /// * `byte` - The command being written to HD44780 hardware.
     /// * `delay` - The expected delay(µs) after sending command so hardware has
-    /// time to process it.
+    ///   time to process it.
     ///
     /// Implementing drivers may ignore `delay` if the under-laying interface
     /// is slower than the command time.
@@ -94,19 +94,82 @@ pub trait HD44780: Write {
     /// * `dc_mode` - Same as display mode bit flags used with [display_control()] command.
     /// * `ems_mode` - Same as entry mode bit flags used with [entry_mode_set()] command.
     ///
+    /// ## Remarks
+    ///
+    /// The default implementation calls [prepare_init()] and then sends the
+    /// wake up sequence for a [bus_len()] bit bus followed by the final
+    /// [function_set()], [display_control()], [entry_mode_set()], and
+    /// [clear_display()] commands so most drivers only need to provide those
+    /// two methods.
+    /// Drivers for interfaces that do their own reset, like serial backpacks,
+    /// replace it instead.
+    ///
+    /// ## Errors
+    /// Returns [InvalidDataBusLen] if the bus is not 4 or 8 bits or 8 bit mode
+    /// is asked for on a 4 bit bus.
+    ///
     /// [HD44780]: https://en.wikipedia.org/wiki/Hitachi_HD44780_LCD_controller#Mode_selection
+    /// [prepare_init()]: trait.HD44780.html#method.prepare_init
+    /// [bus_len()]: trait.HD44780.html#method.bus_len
     /// [function_set()]: trait.HD44780.html#method.function_set
     /// [display_control()]: trait.HD44780.html#method.display_control
     /// [entry_mode_set()]: trait.HD44780.html#method.entry_mode_set
+    /// [clear_display()]: trait.HD44780.html#method.clear_display
+    /// [InvalidDataBusLen]: enum.HdError.html#variant.InvalidDataBusLen
     ///
     fn init<FM, DM, EM>(&mut self, fs_mode: FM, dc_mode: DM, ems_mode: EM) -> Result
     where
         FM: Into<Option<FunctionMode>>,
         DM: Into<Option<DisplayMode>>,
-        EM: Into<Option<EntryMode>>;
+        EM: Into<Option<EntryMode>>,
+    {
+        let fs = fs_mode.into().unwrap_or_default();
+        let dc = dc_mode.into().unwrap_or_default();
+        let ems = ems_mode.into().unwrap_or_default();
+        self.prepare_init()?;
+        let bus_len = self.bus_len();
+        init_sequence(self, bus_len, fs, dc, ems)
+    }
+    /// Number of data bus pins (4 or 8) used to talk to the display.
+    ///
+    /// Used by the default [init()] to pick the wake up sequence.
+    /// Defaults to 8 which also suits drivers that do not have a real bus.
+    ///
+    /// [init()]: #method.init
+    ///
+    fn bus_len(&self) -> usize {
+        8
+    }
+    /// Get the driver ready for the reset sequence in the default [init()].
+    ///
+    /// Drivers use this to wait for the display to stabilize after power on
+    /// and to put their interface into a known state.
+    /// Does nothing by default.
+    ///
+    /// [init()]: #method.init
+    ///
+    fn prepare_init(&mut self) -> Result {
+        Ok(())
+    }
     //
     // ## Shouldn't need to change these in driver implementations. ##
     //
+    /// Send a single typed instruction through [command()].
+    ///
+    /// This is __NOT__ part of the actual HD44780 command set but is what all
+    /// of the command methods below use so every driver gets the same command
+    /// bytes and delays.
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// lcd.instruction(Instruction::SetDdRamAddr(0x40))?;
+    /// ```
+    ///
+    /// [command()]: #tymethod.command
+    ///
+    fn instruction(&mut self, instruction: Instruction) -> Result {
+        self.command(instruction.encode(), instruction.delay(Self::COMMAND_DELAY))
+    }
     /// Clear the display.
     ///
     /// From HD44780 datasheet:
@@ -117,11 +180,7 @@ pub trait HD44780: Write {
     /// lcd.clear_display()?;
     /// ```
     fn clear_display(&mut self) -> Result {
-        let cmd: u8 = Self::CLEAR_DISPLAY;
-        // Per HD44780 datasheet clear takes 1.52ms.
-        // 37µs * 42 = 1.554ms for a little fudge factor.
-        let delay = Self::COMMAND_DELAY * 42;
-        self.command(cmd, delay)
+        self.instruction(Instruction::ClearDisplay)
     }
     /// Used to shift the display or the cursor to the left or right.
     ///
@@ -138,9 +197,7 @@ pub trait HD44780: Write {
     where
     SM: Into<ShiftMode>
     {
-        let mode = mode.into();
-        let cmd: u8 = Self::CURSOR_SHIFT | mode.bits();
-        self.command(cmd, Self::COMMAND_DELAY)
+        self.instruction(Instruction::CursorShift(mode.into()))
     }
     /// Set display on/off controls.
     ///
@@ -157,9 +214,7 @@ pub trait HD44780: Write {
     where
         DM: Into<DisplayMode>
     {
-        let mode = mode.into();
-        let cmd = Self::DISPLAY_CONTROL | mode.bits();
-        self.command(cmd, Self::COMMAND_DELAY)
+        self.instruction(Instruction::DisplayControl(mode.into()))
     }
     /// Sets data cursor direction and display shifting.
     ///
@@ -176,9 +231,7 @@ pub trait HD44780: Write {
     where
     EM: Into<EntryMode>
     {
-        let mode = mode.into();
-        let cmd: u8 = Self::ENTRY_MODE_SET | mode.bits();
-        self.command(cmd, Self::COMMAND_DELAY)
+        self.instruction(Instruction::EntryModeSet(mode.into()))
     }
     /// Used to initialize the interface size (4, 8 bit), display line count, and font.
     ///
@@ -209,8 +262,7 @@ pub trait HD44780: Write {
         if mode.contains(FunctionMode::LINES_2) && mode.contains(FunctionMode::DOTS_5X10) {
             return Err(InvalidLineAndFontMode);
         }
-        let cmd: u8 = Self::FUNCTION_SET | mode.bits();
-        self.command(cmd, Self::COMMAND_DELAY)
+        self.instruction(Instruction::FunctionSet(mode))
     }
     /// Reset the cursor to home position.
     ///
@@ -224,11 +276,7 @@ pub trait HD44780: Write {
     /// lcd.return_home()?;
     /// ```
     fn return_home(&mut self) -> Result {
-        let cmd: u8 = Self::RETURN_HOME;
-        // Per HD44780 datasheet home takes 1.52ms.
-        // 37µs * 42 = 1.554ms for a little fudge factor.
-        let delay = Self::COMMAND_DELAY * 42;
-        self.command(cmd, delay)
+        self.instruction(Instruction::ReturnHome)
     }
     /// Set CG RAM(Custom Char) address.
     ///
@@ -243,9 +291,34 @@ pub trait HD44780: Write {
     /// lcd.set_cg_ram_addr(location)?;
     /// ```
     fn set_cg_ram_addr(&mut self, address: u8) -> Result {
-        let address = address & 0b0011_1111;
-        let cmd: u8 = Self::SET_CG_RAM_ADDR | address;
-        self.command(cmd, Self::COMMAND_DELAY)
+        self.instruction(Instruction::SetCgRamAddr(address))
+    }
+    /// Move the cursor to a column and row of the display.
+    ///
+    /// This is __NOT__ part of the actual HD44780 command set but a helper
+    /// which uses [set_dd_ram_addr()] after working out the address.
+    ///
+    /// ## Arguments
+    /// * `geometry` - Layout of the display.
+    /// * `col` - Column starting from 0.
+    /// * `row` - Row starting from 0.
+    ///
+    /// ## Errors
+    /// Unlike [set_dd_ram_addr()] positions outside of the display are __NOT__
+    /// masked and return an [OffScreen] error instead.
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// // Start of the 4th row on a 20x4 display.
+    /// lcd.set_cursor(&Geometry::LCD_20X4, 0, 3)?;
+    /// ```
+    ///
+    /// [set_dd_ram_addr()]: #method.set_dd_ram_addr
+    /// [OffScreen]: enum.HdError.html#variant.OffScreen
+    ///
+    fn set_cursor(&mut self, geometry: &Geometry, col: u8, row: u8) -> Result {
+        let address = geometry.address(col, row)?;
+        self.set_dd_ram_addr(address)
     }
     /// Set DD RAM(Display) address.
     ///
@@ -253,16 +326,46 @@ pub trait HD44780: Write {
     /// Sets DD RAM address.
     /// DD RAM data is sent and received after this setting.
     ///
+    /// ## Errors
+    /// Returns [InvalidDdRamAddr] if bit 7 of the address is set.
+    /// That bit picks the bottom controller of a [Geometry::LCD_40X4] display
+    /// so only drivers with two controllers, like [DualGpioDriver], accept it.
+    ///
     /// ## Examples
     /// ```edition2018,ignore
     /// // Start of the 2nd line on 2 line display.
     /// let location = 0x40;
     /// lcd.set_dd_ram_addr(location)?;
     /// ```
+    ///
+    /// [InvalidDdRamAddr]: enum.HdError.html#variant.InvalidDdRamAddr
+    /// [Geometry::LCD_40X4]: struct.Geometry.html#associatedconstant.LCD_40X4
+    /// [DualGpioDriver]: struct.DualGpioDriver.html
+    ///
     fn set_dd_ram_addr(&mut self, address: u8) -> Result {
-        let address = address & 0b0111_1111;
-        let cmd: u8 = Self::SET_DD_RAM_ADDR | address;
-        self.command(cmd, Self::COMMAND_DELAY)
+        if address & 0b1000_0000 != 0 {
+            return Err(InvalidDdRamAddr(address));
+        }
+        self.instruction(Instruction::SetDdRamAddr(address))
+    }
+    /// Write text to the display converting it to character ROM codes first.
+    ///
+    /// This is __NOT__ part of the actual HD44780 command set but a helper
+    /// because writing a `&str` directly only works for plain ASCII.
+    ///
+    /// ## Arguments
+    /// * `text` - The text to write.
+    /// * `charset` - Encoder for the character ROM the display has.
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// let charset = Charset::new(CharRom::A00, Fallback::Transliterate);
+    /// lcd.write_str("25°C", &charset)?;
+    /// ```
+    fn write_str(&mut self, text: &str, charset: &Charset) -> Result {
+        let bytes = charset.encode(text)?;
+        self.write_all(&bytes)?;
+        Ok(())
     }
     // Commands
     const CLEAR_DISPLAY: u8 = 0x01;
@@ -275,6 +378,173 @@ pub trait HD44780: Write {
     const SET_DD_RAM_ADDR: u8 = 0x80;
 }
 
+/// Read back support for drivers that can read from the HD44780 controller.
+///
+/// Only available on drivers which have the display `RW` input connected and
+/// can switch the data bus to inputs.
+///
+/// All of the provided helpers assume the entry mode is set to increment the
+/// address counter and that the controller was in DD RAM mode when called.
+pub trait ReadableHD44780: HD44780 {
+    /// Read the current address counter.
+    ///
+    /// From HD44780 datasheet:
+    /// Reads busy flag indicating internal operation is being performed and
+    /// reads address counter contents.
+    ///
+    /// Only the 7 bit address is returned as the driver already waits for the
+    /// busy flag to clear.
+    fn read_address_counter(&mut self) -> Result<u8>;
+    /// Read `len` bytes from DD or CG RAM starting at the current address.
+    ///
+    /// From HD44780 datasheet:
+    /// Reads data from DD RAM or CG RAM.
+    ///
+    /// Which RAM is read depends on if [set_dd_ram_addr()] or
+    /// [set_cg_ram_addr()] was used last.
+    ///
+    /// [set_dd_ram_addr()]: trait.HD44780.html#method.set_dd_ram_addr
+    /// [set_cg_ram_addr()]: trait.HD44780.html#method.set_cg_ram_addr
+    ///
+    fn read_data(&mut self, len: usize) -> Result<Vec<u8>>;
+    /// Read all 80 bytes of DD RAM.
+    ///
+    /// Bytes are in address counter order so in 2 line mode the first 40 bytes
+    /// are from the first line (`0x00`-`0x27`) and the last 40 bytes are from
+    /// the second line (`0x40`-`0x67`).
+    /// In 1 line mode they are addresses `0x00`-`0x4F`.
+    ///
+    /// The address counter is restored afterwards.
+    fn dump_dd_ram(&mut self) -> Result<Vec<u8>> {
+        let address = self.read_address_counter()?;
+        self.set_dd_ram_addr(0x00)?;
+        let result = self.read_data(80);
+        self.set_dd_ram_addr(address)?;
+        result
+    }
+    /// Read all 64 bytes of CG RAM.
+    ///
+    /// Each group of 8 bytes is one of the custom characters with the top row
+    /// first.
+    ///
+    /// The DD RAM address counter is restored afterwards.
+    fn dump_cg_ram(&mut self) -> Result<Vec<u8>> {
+        let address = self.read_address_counter()?;
+        self.set_cg_ram_addr(0x00)?;
+        let result = self.read_data(64);
+        self.set_dd_ram_addr(address)?;
+        result
+    }
+    /// Read what is currently being shown on a display as rows of bytes.
+    ///
+    /// Any display shift is ignored.
+    ///
+    /// The address counter is restored afterwards.
+    ///
+    /// ## Arguments
+    /// * `geometry` - Layout of the display.
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// for row in lcd.capture_screen(&Geometry::LCD_16X2)? {
+    ///     println!("{}", String::from_utf8_lossy(&row));
+    /// }
+    /// ```
+    fn capture_screen(&mut self, geometry: &Geometry) -> Result<Vec<Vec<u8>>> {
+        let address = self.read_address_counter()?;
+        let mut screen = Vec::new();
+        for row in 0..geometry.rows.min(4) {
+            let mut line = Vec::with_capacity(geometry.columns as usize);
+            let mut col = 0;
+            // Rows can be split into runs of addresses like on 16x1 type 1.
+            while col < geometry.columns {
+                let start = geometry.address(col, row)?;
+                let mut len = 1;
+                while col + len < geometry.columns
+                    && geometry.address(col + len, row)? == start + len
+                {
+                    len += 1;
+                }
+                self.set_dd_ram_addr(start)?;
+                match self.read_data(len as usize) {
+                    Ok(data) => line.extend(data),
+                    Err(e) => {
+                        self.set_dd_ram_addr(address)?;
+                        return Err(e);
+                    }
+                }
+                col += len;
+            }
+            screen.push(line);
+        }
+        self.set_dd_ram_addr(address)?;
+        Ok(screen)
+    }
+}
+
+/// Runs the reset and mode setting sequence used by the default [init()].
+///
+/// Drivers are expected to have already waited for the display to stabilize
+/// after power on before calling this.
+///
+/// ## Arguments
+/// * `lcd` - The driver the commands are sent through.
+/// * `bus_len` - Number of data bus pins (4 or 8) the driver is using.
+/// * `fs` - Function mode used for the final [function_set()].
+/// * `dc` - Display mode used for the final [display_control()].
+/// * `ems` - Entry mode used for the final [entry_mode_set()].
+///
+/// [init()]: trait.HD44780.html#method.init
+/// [function_set()]: trait.HD44780.html#method.function_set
+/// [display_control()]: trait.HD44780.html#method.display_control
+/// [entry_mode_set()]: trait.HD44780.html#method.entry_mode_set
+///
+pub(crate) fn init_sequence<T>(
+    lcd: &mut T,
+    bus_len: usize,
+    fs: FunctionMode,
+    dc: DisplayMode,
+    ems: EntryMode,
+) -> Result
+where
+    T: HD44780 + ?Sized,
+{
+    // The display can be in any of 3 states at this point and the follow
+    // sequence of commands should get it into a known and usable state.
+    // ## Phase 1 ##
+    let mut cmd = 0x33u8;
+    // Wait at least 4.1ms before issuing next instruction.
+    // 100 times command delay should work.
+    let mut delay = T::COMMAND_DELAY * 100;
+    lcd.command(cmd, delay)?;
+    // ## Phase 2 ##
+    match bus_len {
+        4 => {
+            if fs.contains(FunctionMode::BITS_8) {
+                return Err(InvalidDataBusLen);
+            }
+            cmd = 0x32;
+        }
+        8 => {
+            cmd = 0x33;
+        }
+        _ => {
+            return Err(InvalidDataBusLen);
+        }
+    }
+    // Wait at least 100us before sending last special initialization command.
+    // 3 times command delay should work.
+    delay = T::COMMAND_DELAY * 3;
+    lcd.command(cmd, delay)?;
+    // ## Final Phase ##
+    // Now the display is in a know state and the additional regular
+    // commands can be sent to it.
+    lcd.function_set(fs)?;
+    lcd.display_control(dc)?;
+    lcd.entry_mode_set(ems)?;
+    lcd.clear_display()
+}
+
 // DisplayMode
 bitflags! {
     /// Display mode bit flags used with [display_control()] command.
@@ -0,0 +1,134 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Contains custom character bitmaps for CG RAM.
+
+use crate::error::HdError::InvalidCustomChar;
+use crate::Result;
+use std::str::FromStr;
+
+/// A custom character bitmap which can be loaded into CG RAM.
+///
+/// Each row is the lower 5 bits of a byte with bit 4 being the left most pixel.
+/// Characters for the 5x8 font have 8 rows with the last being the cursor line
+/// and ones for the 5x10 font have 11 rows.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let heart: CustomChar = "
+///     .....
+///     .#.#.
+///     #####
+///     #####
+///     .###.
+///     ..#..
+///     .....
+///     .....
+/// ".parse()?;
+/// lcd.load_custom_char(0, &heart)?;
+/// ```
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CustomChar {
+    rows: [u8; 11],
+    tall: bool,
+}
+
+impl CustomChar {
+    /// Create a custom character for the 5x8 font.
+    ///
+    /// ## Arguments
+    /// * `rows` - Pixel rows from top to bottom, only the lower 5 bits are used.
+    pub fn new(rows: [u8; 8]) -> Self {
+        let mut all = [0; 11];
+        all[..8].copy_from_slice(&rows);
+        Self::masked(all, false)
+    }
+    /// Create a custom character for the 5x10 font.
+    ///
+    /// ## Arguments
+    /// * `rows` - Pixel rows from top to bottom, only the lower 5 bits are used.
+    pub fn new_5x10(rows: [u8; 11]) -> Self {
+        Self::masked(rows, true)
+    }
+    /// Create a custom character from ASCII art.
+    ///
+    /// Each non-blank line is a row with `#` for pixels that are on and `.` for
+    /// ones that are off.
+    /// Leading and trailing whitespace on each line is ignored.
+    /// Lines can be shorter than 5 pixels in which case the rest are off.
+    ///
+    /// 7 or 8 rows make a 5x8 character and 10 or 11 rows a 5x10 one.
+    ///
+    /// ## Errors
+    /// Returns [InvalidCustomChar] for any other characters, rows longer than 5
+    /// pixels, or the wrong number of rows.
+    ///
+    /// [InvalidCustomChar]: enum.HdError.html#variant.InvalidCustomChar
+    ///
+    pub fn from_art(art: &str) -> Result<Self> {
+        let mut rows = Vec::with_capacity(11);
+        for line in art.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if line.chars().count() > 5 {
+                return Err(InvalidCustomChar("rows can be at most 5 pixels"));
+            }
+            let mut row = 0u8;
+            for (i, c) in line.chars().enumerate() {
+                match c {
+                    '#' => row |= 0x10 >> i,
+                    '.' => {}
+                    _ => return Err(InvalidCustomChar("only '#' and '.' are allowed")),
+                }
+            }
+            rows.push(row);
+        }
+        let tall = match rows.len() {
+            7 | 8 => false,
+            10 | 11 => true,
+            _ => return Err(InvalidCustomChar("must have 7, 8, 10, or 11 rows")),
+        };
+        let mut all = [0; 11];
+        all[..rows.len()].copy_from_slice(&rows);
+        Ok(Self::masked(all, tall))
+    }
+    /// Pixel rows from top to bottom, either 8 or 11 of them.
+    pub fn rows(&self) -> &[u8] {
+        if self.tall {
+            &self.rows
+        } else {
+            &self.rows[..8]
+        }
+    }
+    /// If this is a character for the 5x10 font.
+    pub fn is_5x10(&self) -> bool {
+        self.tall
+    }
+    fn masked(mut rows: [u8; 11], tall: bool) -> Self {
+        rows.iter_mut().for_each(|row| *row &= 0x1F);
+        CustomChar { rows, tall }
+    }
+}
+
+impl FromStr for CustomChar {
+    type Err = crate::HdError;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_art(s)
+    }
+}
@@ -0,0 +1,249 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Generic blocking driver that uses the Linux GPIO character device.
+//!
+//! The older sysfs GPIO interface is deprecated and missing from newer
+//! kernels. With the character device all of the display pins can be
+//! requested together as a single line handle so the whole bus and enable are
+//! set with one `ioctl` call. Each nibble (or byte) takes two of them, one to
+//! raise enable with the data and one to drop it again.
+//!
+//! Support for the [gpio-cdev] crate's `MultiLineHandle` is enabled with the
+//! `cdev` feature.
+//!
+//! [gpio-cdev]: https://crates.io/crates/gpio-cdev
+
+use crate::cmd::HD44780;
+use crate::driver::gpio_driver::RegisterSelect;
+use crate::error::HdError::InvalidDataBusLen;
+use crate::Result;
+use embedded_hal::blocking::delay::DelayUs;
+use std::io::Write;
+
+// Positions in the line values of the control lines.
+const RS: usize = 0;
+const E: usize = 1;
+const DATA: usize = 2;
+
+/// A group of GPIO output lines which are all set with a single call.
+///
+/// The lines must be in the order `RS`, `E`, and then the 4 or 8 data lines
+/// starting with the lowest bit (`D4` or `D0`).
+pub trait LineHandle {
+    /// Number of lines in the handle.
+    fn num_lines(&self) -> usize;
+    /// Set all of the lines at once, `0` for low and `1` for high.
+    fn set_values(&mut self, values: &[u8]) -> Result;
+}
+
+#[cfg(feature = "cdev")]
+impl LineHandle for gpio_cdev::MultiLineHandle {
+    fn num_lines(&self) -> usize {
+        gpio_cdev::MultiLineHandle::num_lines(self)
+    }
+    fn set_values(&mut self, values: &[u8]) -> Result {
+        gpio_cdev::MultiLineHandle::set_values(self, values)
+            .map_err(|_| crate::HdError::GpioLines("set"))
+    }
+}
+
+/// This is the driver used for HD44780 displays connected to GPIO lines of a
+/// Linux GPIO character device (`/dev/gpiochipN`).
+///
+/// ## Remarks
+///
+/// Each nibble (or byte) is put on the bus together with enable high in a
+/// single call and then enable is set low with a second call.
+/// The register select line is changed with enable low before any command.
+///
+/// Like [GpioDriver] this driver assumes that the `RW` input on the display is
+/// pulled to `GND`.
+///
+/// [GpioDriver]: struct.GpioDriver.html
+///
+#[derive(Debug)]
+pub struct CdevDriver<L, D>
+where
+    L: LineHandle,
+    D: DelayUs<u16>,
+{
+    lines: L,
+    values: Vec<u8>,
+    delay: D,
+}
+
+impl<L, D> CdevDriver<L, D>
+where
+    L: LineHandle,
+    D: DelayUs<u16>,
+{
+    /// Create a new instance of driver.
+    ///
+    /// ## Arguments
+    ///
+    /// * `lines` - An already requested set of output lines in the order
+    ///   `RS`, `E`, then the data lines. Only 4 or 8 data lines can be used.
+    /// * `delay` - Used to give the display time to process commands.
+    ///
+    /// ## Errors
+    /// Returns [InvalidDataBusLen] if `lines` does not have 6 or 10 lines.
+    ///
+    /// [InvalidDataBusLen]: enum.HdError.html#variant.InvalidDataBusLen
+    ///
+    pub fn new(lines: L, delay: D) -> Result<Self> {
+        let mut values = vec![0; lines.num_lines()];
+        if values.len() != DATA + 4 && values.len() != DATA + 8 {
+            return Err(InvalidDataBusLen);
+        }
+        // Start out in data mode the same as after any command.
+        values[RS] = RegisterSelect::Data as u8;
+        Ok(CdevDriver {
+            lines,
+            values,
+            delay,
+        })
+    }
+    fn set_control_bits(&mut self, ctrl: RegisterSelect) -> Result {
+        self.values[RS] = ctrl as u8;
+        self.values[E] = 0;
+        self.lines.set_values(&self.values)
+    }
+    fn write_bus(&mut self, bits: u8) -> Result {
+        for (i, value) in self.values[DATA..].iter_mut().enumerate() {
+            *value = (bits >> i) & 1;
+        }
+        // Data is set along with enable and read by the display on the
+        // falling edge.
+        self.values[E] = 1;
+        self.lines.set_values(&self.values)?;
+        // Minimum time is approximately 1µs.
+        self.delay.delay_us(1u16);
+        self.values[E] = 0;
+        self.lines.set_values(&self.values)?;
+        // Given a little time to ensure low state is seen.
+        self.delay.delay_us(1u16);
+        Ok(())
+    }
+    fn write_byte(&mut self, byte: u8) -> Result {
+        match self.bus_len() {
+            4 => {
+                self.write_bus(byte >> 4)?;
+                self.write_bus(byte & 0b0000_1111)
+            }
+            8 => self.write_bus(byte),
+            _ => Err(InvalidDataBusLen),
+        }
+    }
+}
+
+#[cfg(feature = "cdev")]
+impl<D> CdevDriver<gpio_cdev::MultiLineHandle, D>
+where
+    D: DelayUs<u16>,
+{
+    /// Request the display lines from a GPIO chip and create a new instance
+    /// of driver.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chip` - An open GPIO chip like `/dev/gpiochip0`.
+    /// * `rs` - Line offset connected to the register select input on display.
+    /// * `e` - Line offset connected to the enable input on display.
+    /// * `data` - Line offsets connected to the data inputs of the display
+    ///   starting with the lowest bit. Only 4 or 8 lines can be used.
+    /// * `delay` - Used to give the display time to process commands.
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// let mut chip = Chip::new("/dev/gpiochip0")?;
+    /// let lcd = CdevDriver::request(&mut chip, 27, 22, &[25, 24, 23, 18], Delay)?;
+    /// ```
+    pub fn request(
+        chip: &mut gpio_cdev::Chip,
+        rs: u32,
+        e: u32,
+        data: &[u32],
+        delay: D,
+    ) -> Result<Self> {
+        let mut offsets = vec![rs, e];
+        offsets.extend_from_slice(data);
+        let mut defaults = vec![0; offsets.len()];
+        defaults[RS] = RegisterSelect::Data as u8;
+        let lines = chip
+            .get_lines(&offsets)
+            .and_then(|l| {
+                l.request(
+                    gpio_cdev::LineRequestFlags::OUTPUT,
+                    &defaults,
+                    "hd44780-ntb",
+                )
+            })
+            .map_err(|_| crate::HdError::GpioLines("request"))?;
+        Self::new(lines, delay)
+    }
+}
+
+impl<L, D> HD44780 for CdevDriver<L, D>
+where
+    L: LineHandle,
+    D: DelayUs<u16>,
+{
+    const COMMAND_DELAY: u16 = 41;
+    fn command(&mut self, byte: u8, delay: u16) -> Result {
+        // Switch to command mode.
+        self.set_control_bits(RegisterSelect::Cmnd)?;
+        // Send command.
+        self.write_byte(byte)?;
+        // Given HD44780 time to process command before sending anything else.
+        self.delay.delay_us(delay);
+        // Switch back to data mode.
+        self.set_control_bits(RegisterSelect::Data)?;
+        Ok(())
+    }
+    fn bus_len(&self) -> usize {
+        self.values.len() - DATA
+    }
+    fn prepare_init(&mut self) -> Result {
+        // Insure display has had time to stabilize if just powered on.
+        // This takes between 15 to 40ms depending on supplied voltage.
+        // 1000 times the command delay should be enough.
+        let delay = Self::COMMAND_DELAY * 1000;
+        self.delay.delay_us(delay);
+        Ok(())
+    }
+}
+
+impl<L, D> Write for CdevDriver<L, D>
+where
+    L: LineHandle,
+    D: DelayUs<u16>,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for byte in buf {
+            self.write_byte(*byte)?;
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
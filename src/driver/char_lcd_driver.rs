@@ -0,0 +1,284 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Driver for displays owned by the Linux kernel `hd44780` auxdisplay driver.
+//!
+//! When the kernel driver is used it exposes the display as `/dev/lcd` which
+//! accepts text mixed with the `charlcd` escape sequences. This driver
+//! translates the HD44780 command set into those escape sequences so the same
+//! application code works with both GPIO and kernel owned displays.
+
+use crate::cmd::HD44780;
+use crate::error::HdError::Unsupported;
+use crate::{DisplayMode, EntryMode, FunctionMode, Instruction, Result, ShiftMode};
+use std::convert::TryFrom;
+use std::io::Write;
+
+/// Width used by the kernel for each line of DD RAM when none is configured.
+pub const CHARLCD_BUFFER_WIDTH: u8 = 40;
+
+/// This is the driver used for displays behind the Linux `charlcd` interface.
+///
+/// ## Remarks
+///
+/// The kernel keeps its own cursor position and only understands column and
+/// row positions so [set_dd_ram_addr()] addresses are converted the same way
+/// the kernel converts them back. For 4 row displays the buffer width given to
+/// [with_buffer_width()] must match the `display-width-chars` the kernel was
+/// configured with.
+///
+/// CG RAM data is collected and each changed custom character is uploaded as a
+/// whole using the generator escape sequence at the end of each write.
+///
+/// DD RAM bytes `0x08`-`0x0F` are sent as their `0x00`-`0x07` CG RAM mirrors
+/// so they aren't taken as control characters by the kernel.
+///
+/// The kernel only supports left to right entry without display shift so any
+/// other entry mode returns an error.
+///
+/// [set_dd_ram_addr()]: trait.HD44780.html#method.set_dd_ram_addr
+/// [with_buffer_width()]: #method.with_buffer_width
+///
+#[derive(Debug)]
+pub struct CharLcdDriver<W>
+where
+    W: Write,
+{
+    lcd: W,
+    buffer_width: u8,
+    cg_ram: [u8; 64],
+    cg_address: Option<u8>,
+    dirty: u8,
+}
+
+impl<W> CharLcdDriver<W>
+where
+    W: Write,
+{
+    /// Create a new instance of driver.
+    ///
+    /// Same as calling [with_buffer_width()] with [CHARLCD_BUFFER_WIDTH].
+    ///
+    /// ## Arguments
+    ///
+    /// * `lcd` - Where the text and escape sequences are written. Normally an
+    ///   open `/dev/lcd` file.
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// let file = OpenOptions::new().write(true).open("/dev/lcd")?;
+    /// let mut lcd = CharLcdDriver::new(file);
+    /// ```
+    ///
+    /// [with_buffer_width()]: #method.with_buffer_width
+    /// [CHARLCD_BUFFER_WIDTH]: constant.CHARLCD_BUFFER_WIDTH.html
+    ///
+    pub fn new(lcd: W) -> CharLcdDriver<W> {
+        Self::with_buffer_width(lcd, CHARLCD_BUFFER_WIDTH)
+    }
+    /// Create a new instance of driver for a kernel configured buffer width.
+    ///
+    /// ## Arguments
+    ///
+    /// * `lcd` - Where the text and escape sequences are written.
+    /// * `buffer_width` - Characters per row the kernel was configured with.
+    ///   Rows 2 and 3 of a 4 row display start this far into DD RAM lines.
+    ///
+    pub fn with_buffer_width(lcd: W, buffer_width: u8) -> CharLcdDriver<W> {
+        CharLcdDriver {
+            lcd,
+            buffer_width,
+            cg_ram: [0; 64],
+            cg_address: None,
+            dirty: 0,
+        }
+    }
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.lcd
+    }
+    /// Turn the backlight on or off.
+    pub fn set_backlight(&mut self, on: bool) -> Result {
+        self.escape(if on { "+" } else { "-" })
+    }
+    fn escape(&mut self, code: &str) -> Result {
+        write!(self.lcd, "\x1b[L{}", code)?;
+        Ok(())
+    }
+    /// Convert a DD RAM address into the column and row used by the kernel.
+    fn position(&self, address: u8) -> (u8, u8) {
+        let (mut x, mut y) = (address, 0);
+        if x >= 0x40 {
+            x -= 0x40;
+            y |= 1;
+        }
+        if x >= self.buffer_width {
+            x -= self.buffer_width;
+            y |= 2;
+        }
+        (x, y)
+    }
+    fn flush_cg_ram(&mut self) -> Result {
+        for c in 0..8u8 {
+            if self.dirty & (1 << c) == 0 {
+                continue;
+            }
+            let mut code = format!("G{}", c);
+            let start = c as usize * 8;
+            for row in self.cg_ram[start..start + 8].iter() {
+                code.push_str(&format!("{:02x}", row));
+            }
+            code.push(';');
+            self.escape(&code)?;
+        }
+        self.dirty = 0;
+        Ok(())
+    }
+}
+
+impl<W> HD44780 for CharLcdDriver<W>
+where
+    W: Write,
+{
+    // The kernel takes care of any needed delays.
+    const COMMAND_DELAY: u16 = 0;
+    fn command(&mut self, byte: u8, _delay: u16) -> Result {
+        let instruction = match Instruction::try_from(byte) {
+            Ok(instruction) => instruction,
+            // Only 0x00 is left which isn't a command.
+            Err(_) => return Ok(()),
+        };
+        match instruction {
+            Instruction::SetDdRamAddr(address) => {
+                self.cg_address = None;
+                let (x, y) = self.position(address);
+                self.escape(&format!("x{}y{};", x, y))
+            }
+            Instruction::SetCgRamAddr(address) => {
+                self.cg_address = Some(address);
+                Ok(())
+            }
+            Instruction::FunctionSet(mode) => {
+                self.escape(if mode.contains(FunctionMode::LINES_2) {
+                    "N"
+                } else {
+                    "n"
+                })?;
+                self.escape(if mode.contains(FunctionMode::DOTS_5X10) {
+                    "F"
+                } else {
+                    "f"
+                })
+            }
+            Instruction::CursorShift(mode) => {
+                let display = mode.contains(ShiftMode::DISPLAY_MOVE);
+                let right = mode.contains(ShiftMode::MOVE_RIGHT);
+                self.escape(match (display, right) {
+                    (false, false) => "l",
+                    (false, true) => "r",
+                    (true, false) => "L",
+                    (true, true) => "R",
+                })
+            }
+            Instruction::DisplayControl(mode) => {
+                let flag =
+                    |on, set: &'static str, clear: &'static str| if on { set } else { clear };
+                let code = [
+                    flag(mode.contains(DisplayMode::DISPLAY_ON), "D", "d"),
+                    flag(mode.contains(DisplayMode::CURSOR_ON), "C", "c"),
+                    flag(mode.contains(DisplayMode::BLINK_ON), "B", "b"),
+                ];
+                for c in code.iter() {
+                    self.escape(c)?;
+                }
+                Ok(())
+            }
+            Instruction::EntryModeSet(mode) => {
+                if mode != EntryMode::default() {
+                    return Err(Unsupported("Entry mode other than left to right"));
+                }
+                Ok(())
+            }
+            Instruction::ReturnHome => {
+                self.cg_address = None;
+                write!(self.lcd, "\x1b[H")?;
+                Ok(())
+            }
+            Instruction::ClearDisplay => {
+                self.cg_address = None;
+                write!(self.lcd, "\x1b[2J")?;
+                Ok(())
+            }
+        }
+    }
+    fn init<FM, DM, EM>(&mut self, fs_mode: FM, dc_mode: DM, ems_mode: EM) -> Result
+    where
+        FM: Into<Option<FunctionMode>>,
+        DM: Into<Option<DisplayMode>>,
+        EM: Into<Option<EntryMode>>,
+    {
+        let fs = fs_mode.into().unwrap_or_default();
+        let dc = dc_mode.into().unwrap_or_default();
+        let ems = ems_mode.into().unwrap_or_default();
+        // The kernel does the actual reset sequence.
+        self.escape("I")?;
+        self.cg_address = None;
+        self.function_set(fs)?;
+        self.display_control(dc)?;
+        self.entry_mode_set(ems)?;
+        self.clear_display()
+    }
+}
+
+impl<W> Write for CharLcdDriver<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.cg_address {
+            Some(mut address) => {
+                for byte in buf {
+                    self.cg_ram[address as usize] = *byte;
+                    self.dirty |= 1 << (address / 8);
+                    address = (address + 1) & 0b0011_1111;
+                }
+                self.cg_address = Some(address);
+                self.flush_cg_ram()?;
+            }
+            None => {
+                let text: Vec<u8> = buf
+                    .iter()
+                    .map(|b| match b {
+                        // CG RAM characters are mirrored at 0x08-0x0F.
+                        0x08..=0x0F => b & 0b0000_0111,
+                        0x1B => b' ',
+                        _ => *b,
+                    })
+                    .collect();
+                self.lcd.write_all(&text)?;
+            }
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.lcd.flush()
+    }
+}
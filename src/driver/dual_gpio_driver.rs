@@ -0,0 +1,280 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Generic blocking driver for 40x4 displays with two HD44780 controllers.
+//!
+//! These displays are really two 40x2 displays in one, sharing the `RS` and
+//! data bus pins with separate `E1` and `E2` enable pins to select which of
+//! the two controllers is being talked to.
+
+use crate::cmd::HD44780;
+use crate::driver::gpio_driver::{set_control_bits, write_bus_byte, RegisterSelect};
+use crate::{DisplayMode, Geometry, Instruction, Result, ShiftMode};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::OutputPin;
+use std::convert::TryFrom;
+use std::io::Write;
+
+/// Selects one of the two controllers on a dual controller display.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Controller {
+    /// Controller for the top two rows connected to `E1`.
+    Top,
+    /// Controller for the bottom two rows connected to `E2`.
+    Bottom,
+}
+
+/// This is the driver used for direct GPIO pin connected 40x4 displays.
+///
+/// The display is presented as a single 4 row display with rows 0 and 1 on
+/// the top controller and rows 2 and 3 on the bottom controller.
+///
+/// ## Remarks
+///
+/// Commands are sent to one or both controllers depending on what they do:
+/// * [init()], [clear_display()], [return_home()], [entry_mode_set()],
+///   [function_set()], display shifts, and CG RAM writes go to both so the two
+///   halves always act like one display.
+/// * [set_dd_ram_addr()], cursor moves, and DD RAM data writes only go to the
+///   active controller.
+/// * [display_control()] goes to both but the cursor and blink settings are
+///   only used on the active controller so only one cursor is ever shown.
+///
+/// [clear_display()] and [return_home()] also make the top controller active.
+///
+/// [set_dd_ram_addr()] uses bit 7 of the address to pick the controller, which
+/// is how [Geometry::LCD_40X4] addresses rows 2 and 3, so [set_cursor()] and
+/// [Lcd] reach all 4 rows when given that geometry.
+///
+/// Like [GpioDriver] this driver assumes that the `RW` input on the display is
+/// pulled to `GND`.
+///
+/// [init()]: #method.init
+/// [clear_display()]: trait.HD44780.html#method.clear_display
+/// [return_home()]: trait.HD44780.html#method.return_home
+/// [entry_mode_set()]: trait.HD44780.html#method.entry_mode_set
+/// [function_set()]: trait.HD44780.html#method.function_set
+/// [set_dd_ram_addr()]: trait.HD44780.html#method.set_dd_ram_addr
+/// [display_control()]: trait.HD44780.html#method.display_control
+/// [Geometry::LCD_40X4]: struct.Geometry.html#associatedconstant.LCD_40X4
+/// [set_cursor()]: #method.set_cursor
+/// [Lcd]: struct.Lcd.html
+/// [GpioDriver]: struct.GpioDriver.html
+///
+#[derive(Debug)]
+pub struct DualGpioDriver<RS, EN, DP, D>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    DP: OutputPin,
+    D: DelayUs<u16>,
+{
+    rs: RS,
+    e1: EN,
+    e2: EN,
+    data: Vec<DP>,
+    delay: D,
+    active: Controller,
+    display_mode: DisplayMode,
+    cg_ram: bool,
+}
+
+impl<RS, EN, DP, D> DualGpioDriver<RS, EN, DP, D>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    DP: OutputPin,
+    D: DelayUs<u16>,
+{
+    /// Create a new instance of driver.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rs` - An already setup output GPIO pin that is connected to the
+    ///   register select input on display.
+    /// * `e1` - An already setup output GPIO pin that is connected to the
+    ///   enable input of the top controller.
+    /// * `e2` - An already setup output GPIO pin that is connected to the
+    ///   enable input of the bottom controller.
+    /// * `data` - An already setup array or Vec of GPIO output pins that are
+    ///   connected to the data inputs of the display. Only 4 or 8 pins should
+    ///   be used.
+    /// * `delay` - Used to give the display time to process commands.
+    ///
+    pub fn new(rs: RS, e1: EN, e2: EN, data: Vec<DP>, delay: D) -> DualGpioDriver<RS, EN, DP, D> {
+        DualGpioDriver {
+            rs,
+            e1,
+            e2,
+            data,
+            delay,
+            active: Controller::Top,
+            display_mode: DisplayMode::default(),
+            cg_ram: false,
+        }
+    }
+    /// Returns which controller DD RAM commands and data are sent to.
+    pub fn active(&self) -> Controller {
+        self.active
+    }
+    /// Change which controller DD RAM commands and data are sent to.
+    ///
+    /// The cursor, if turned on, moves to the newly active controller.
+    pub fn set_active(&mut self, controller: Controller) -> Result {
+        if self.active != controller {
+            self.active = controller;
+            self.display_control(self.display_mode)?;
+        }
+        Ok(())
+    }
+    /// Move the cursor to a column and row of the whole display.
+    ///
+    /// Makes the controller owning the row active and sets its DD RAM address.
+    /// Same as [HD44780::set_cursor()] with [Geometry::LCD_40X4].
+    ///
+    /// ## Arguments
+    /// * `col` - Column from 0 to 39.
+    /// * `row` - Row from 0 to 3.
+    ///
+    /// ## Errors
+    /// Returns [OffScreen] if the position is outside of the display.
+    ///
+    /// [HD44780::set_cursor()]: trait.HD44780.html#method.set_cursor
+    /// [Geometry::LCD_40X4]: struct.Geometry.html#associatedconstant.LCD_40X4
+    /// [OffScreen]: enum.HdError.html#variant.OffScreen
+    ///
+    pub fn set_cursor(&mut self, col: u8, row: u8) -> Result {
+        HD44780::set_cursor(self, &Geometry::LCD_40X4, col, row)
+    }
+    fn write_to(&mut self, controller: Controller, byte: u8) -> Result {
+        let e = match controller {
+            Controller::Top => &mut self.e1,
+            Controller::Bottom => &mut self.e2,
+        };
+        write_bus_byte(byte, &mut self.data[..], e, &mut self.delay)
+    }
+    fn write_to_both(&mut self, byte: u8) -> Result {
+        self.write_to(Controller::Top, byte)?;
+        self.write_to(Controller::Bottom, byte)
+    }
+}
+
+impl<RS, EN, DP, D> HD44780 for DualGpioDriver<RS, EN, DP, D>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    DP: OutputPin,
+    D: DelayUs<u16>,
+{
+    const COMMAND_DELAY: u16 = 41;
+    fn command(&mut self, byte: u8, delay: u16) -> Result {
+        let instruction = Instruction::try_from(byte)?;
+        // Switch to command mode.
+        set_control_bits(&mut self.rs, RegisterSelect::Cmnd)?;
+        // Send command to the controller(s) it is meant for.
+        let active = self.active;
+        let result = match instruction {
+            Instruction::SetDdRamAddr(_) => {
+                self.cg_ram = false;
+                self.write_to(active, byte)
+            }
+            Instruction::SetCgRamAddr(_) => {
+                self.cg_ram = true;
+                self.write_to_both(byte)
+            }
+            Instruction::CursorShift(mode) if !mode.contains(ShiftMode::DISPLAY_MOVE) => {
+                self.write_to(active, byte)
+            }
+            Instruction::DisplayControl(mode) => {
+                self.display_mode = mode;
+                // Only the active controller gets to show a cursor.
+                let hidden = byte & !(DisplayMode::CURSOR_ON | DisplayMode::BLINK_ON).bits();
+                let (top, bottom) = match active {
+                    Controller::Top => (byte, hidden),
+                    Controller::Bottom => (hidden, byte),
+                };
+                self.write_to(Controller::Top, top)
+                    .and_then(|_| self.write_to(Controller::Bottom, bottom))
+            }
+            Instruction::ClearDisplay | Instruction::ReturnHome => {
+                self.cg_ram = false;
+                self.active = Controller::Top;
+                self.write_to_both(byte)
+            }
+            // Function set, display shift, and entry mode.
+            _ => self.write_to_both(byte),
+        };
+        result?;
+        // Given HD44780 time to process command before sending anything else.
+        self.delay.delay_us(delay);
+        // Switch back to data mode.
+        set_control_bits(&mut self.rs, RegisterSelect::Data)?;
+        // The cursor may have moved to the top controller.
+        if active != self.active {
+            self.display_control(self.display_mode)?;
+        }
+        Ok(())
+    }
+    fn bus_len(&self) -> usize {
+        self.data.len()
+    }
+    fn set_dd_ram_addr(&mut self, address: u8) -> Result {
+        let controller = if address & 0x80 == 0 {
+            Controller::Top
+        } else {
+            Controller::Bottom
+        };
+        self.set_active(controller)?;
+        self.instruction(Instruction::SetDdRamAddr(address))
+    }
+    fn prepare_init(&mut self) -> Result {
+        // Insure display has had time to stabilize if just powered on.
+        // This takes between 15 to 40ms depending on supplied voltage.
+        // 1000 times the command delay should be enough.
+        let delay = Self::COMMAND_DELAY * 1000;
+        self.delay.delay_us(delay);
+        self.active = Controller::Top;
+        Ok(())
+    }
+}
+
+impl<RS, EN, DP, D> Write for DualGpioDriver<RS, EN, DP, D>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    DP: OutputPin,
+    D: DelayUs<u16>,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for byte in buf {
+            if self.cg_ram {
+                // Keep custom characters the same on both halves.
+                self.write_to_both(*byte)?;
+            } else {
+                self.write_to(self.active, *byte)?;
+            }
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,398 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Contains a software emulation of a HD44780 controller that does __NOT__
+//! connect to any hardware.
+
+use crate::cmd::ReadableHD44780;
+use crate::{
+    DisplayMode, EntryMode, FunctionMode, Geometry, Instruction, Result, ShiftMode, HD44780,
+};
+use std::convert::TryFrom;
+use std::io::{Result as IOResult, Write};
+
+// Length of each display line in DD RAM for 1 and 2 line modes.
+const LINE_LEN_1: u8 = 80;
+const LINE_LEN_2: u8 = 40;
+// Start address of the second line in 2 line mode.
+const LINE_2_START: u8 = 0x40;
+
+/// Which RAM the address counter currently points into.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Ram {
+    CgRam,
+    DdRam,
+}
+
+/// A driver that emulates the internal state of a HD44780 controller.
+///
+/// DD RAM, CG RAM, the address counter, entry, display, and function modes,
+/// and display shift are all modeled the same way the datasheet describes so
+/// tests can check what would actually be shown on a display.
+///
+/// ## Remarks
+///
+/// The visible window and which DD RAM addresses are shown in it come from a
+/// [Geometry] with display shift moving each line as the datasheet describes.
+/// In 1 line mode only the addresses below `0x40` on the first row show
+/// anything.
+///
+/// Like the real controller after its internal power on reset the display is
+/// off, in 8 bit 1 line mode, and set to increment the address counter.
+///
+/// DD RAM addresses with bit 7 set are kept in a second bank so the two
+/// controllers of a [Geometry::LCD_40X4] display can be emulated. Those can
+/// only be reached with [set_dd_ram_addr()] as command bytes only hold 7
+/// address bits.
+///
+/// [Geometry]: struct.Geometry.html
+/// [Geometry::LCD_40X4]: struct.Geometry.html#associatedconstant.LCD_40X4
+/// [set_dd_ram_addr()]: #method.set_dd_ram_addr
+///
+#[derive(Clone, Debug)]
+pub struct EmulatorDriver {
+    geometry: Geometry,
+    dd_ram: [u8; 256],
+    cg_ram: [u8; 64],
+    address: u8,
+    ram: Ram,
+    shift: u8,
+    display: DisplayMode,
+    entry: EntryMode,
+    function: FunctionMode,
+}
+
+impl EmulatorDriver {
+    /// Create a new instance of driver using the common row offsets.
+    ///
+    /// ## Arguments
+    /// * `columns` - Number of characters per row on the emulated display.
+    /// * `rows` - Number of rows on the emulated display (1 to 4).
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// let mut lcd = EmulatorDriver::new(16, 2);
+    /// lcd.init(FunctionMode::LINES_2, DisplayMode::DISPLAY_ON, None)?;
+    /// write!(lcd, "Hello")?;
+    /// assert_eq!(lcd.text(), "Hello           \n                ");
+    /// ```
+    pub fn new(columns: u8, rows: u8) -> Self {
+        Self::with_geometry(Geometry::new(columns, rows.min(4)))
+    }
+    /// Create a new instance of driver for a display layout.
+    ///
+    /// ## Arguments
+    /// * `geometry` - Layout of the emulated display.
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// let mut lcd = EmulatorDriver::with_geometry(Geometry::LCD_16X1_TYPE1);
+    /// ```
+    pub fn with_geometry(geometry: Geometry) -> Self {
+        EmulatorDriver {
+            geometry,
+            dd_ram: [b' '; 256],
+            cg_ram: [0; 64],
+            address: 0,
+            ram: Ram::DdRam,
+            shift: 0,
+            display: DisplayMode::DISPLAY_OFF,
+            entry: EntryMode::ENTRY_LEFT,
+            function: FunctionMode::BITS_8,
+        }
+    }
+    /// Layout of the emulated display.
+    pub fn geometry(&self) -> &Geometry {
+        &self.geometry
+    }
+    /// Number of characters per row on the emulated display.
+    pub fn columns(&self) -> u8 {
+        self.geometry.columns
+    }
+    /// Number of rows on the emulated display.
+    pub fn rows(&self) -> u8 {
+        self.geometry.rows.min(4)
+    }
+    /// Current value of the address counter.
+    ///
+    /// Can point into either DD or CG RAM depending on which address was last
+    /// set. Bit 7 is set when it points into the second DD RAM bank.
+    pub fn address_counter(&self) -> u8 {
+        self.address
+    }
+    /// If the address counter currently points into CG RAM.
+    pub fn is_cg_ram(&self) -> bool {
+        self.ram == Ram::CgRam
+    }
+    /// Contents of CG RAM with 8 bytes per custom character.
+    pub fn cg_ram(&self) -> &[u8; 64] {
+        &self.cg_ram
+    }
+    /// Pattern of custom character `code` (`0x00`-`0x0F`).
+    ///
+    /// Only the lower 5 bits of each row are used by the display.
+    /// Character codes `0x08`-`0x0F` are the same as `0x00`-`0x07`.
+    pub fn custom_char(&self, code: u8) -> [u8; 8] {
+        let start = (code as usize & 0x07) * 8;
+        let mut pattern = [0; 8];
+        pattern.copy_from_slice(&self.cg_ram[start..start + 8]);
+        pattern
+    }
+    /// Contents of DD RAM at `address`.
+    pub fn dd_ram(&self, address: u8) -> u8 {
+        self.dd_ram[address as usize]
+    }
+    /// Current display mode.
+    pub fn display_mode(&self) -> DisplayMode {
+        self.display
+    }
+    /// Current entry mode.
+    pub fn entry_mode(&self) -> EntryMode {
+        self.entry
+    }
+    /// Current function mode.
+    pub fn function_mode(&self) -> FunctionMode {
+        self.function
+    }
+    /// If characters are shown with the 5x10 font.
+    ///
+    /// The controller ignores the font setting in 2 line mode.
+    pub fn is_5x10(&self) -> bool {
+        self.function.contains(FunctionMode::DOTS_5X10)
+            && !self.function.contains(FunctionMode::LINES_2)
+    }
+    /// Number of positions the display has been shifted left.
+    pub fn display_shift(&self) -> u8 {
+        self.shift
+    }
+    /// Where the cursor is in the visible window as `(column, row)`.
+    ///
+    /// Returns `None` if the address counter is in CG RAM or points at a
+    /// location not currently shown.
+    /// Cursor and blink modes are __NOT__ checked.
+    pub fn cursor(&self) -> Option<(u8, u8)> {
+        if self.ram == Ram::CgRam {
+            return None;
+        }
+        for row in 0..self.rows() {
+            for col in 0..self.columns() {
+                if self.visible_address(col, row) == Some(self.address) {
+                    return Some((col, row));
+                }
+            }
+        }
+        None
+    }
+    /// DD RAM address shown at `col`, `row` if any.
+    ///
+    /// Takes the current line mode and display shift into account but not if
+    /// the display is on.
+    pub fn visible_address(&self, col: u8, row: u8) -> Option<u8> {
+        let address = self.geometry.address(col, row).ok()?;
+        if self.function.contains(FunctionMode::LINES_2) {
+            let pos = address & 0x7F & !LINE_2_START;
+            if pos >= LINE_LEN_2 {
+                return None;
+            }
+            let line = address & (0x80 | LINE_2_START);
+            Some(line | ((pos + self.shift) % LINE_LEN_2))
+        } else if row == 0 && address < LINE_2_START {
+            Some((address + self.shift) % LINE_LEN_1)
+        } else {
+            None
+        }
+    }
+    /// Character codes shown in the visible window as rows of bytes.
+    ///
+    /// Anything not being shown including when the display is off is a space.
+    pub fn glyphs(&self) -> Vec<Vec<u8>> {
+        let on = self.display.contains(DisplayMode::DISPLAY_ON);
+        (0..self.rows())
+            .map(|row| {
+                (0..self.columns())
+                    .map(|col| match self.visible_address(col, row) {
+                        Some(address) if on => self.dd_ram[address as usize],
+                        _ => b' ',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+    /// Text shown in the visible window with rows separated by `\n`.
+    ///
+    /// Character codes outside of `0x20`-`0x7D`, which includes all of the
+    /// custom characters, are shown as `?` as the other codes are different
+    /// between character ROMs.
+    pub fn text(&self) -> String {
+        self.glyphs()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&c| match c {
+                        0x20..=0x7D => c as char,
+                        _ => '?',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    fn line_len(&self) -> u8 {
+        if self.function.contains(FunctionMode::LINES_2) {
+            LINE_LEN_2
+        } else {
+            LINE_LEN_1
+        }
+    }
+    /// Move address counter by one in the given direction.
+    fn step_address(&mut self, increment: bool) {
+        // The DD RAM bank never changes on its own.
+        let bank = self.address & 0x80;
+        self.address &= 0x7F;
+        self.address = match self.ram {
+            Ram::CgRam => {
+                if increment {
+                    self.address.wrapping_add(1) & 0x3F
+                } else {
+                    self.address.wrapping_sub(1) & 0x3F
+                }
+            }
+            Ram::DdRam if self.function.contains(FunctionMode::LINES_2) => {
+                match (increment, self.address) {
+                    (true, 0x27) => LINE_2_START,
+                    (true, 0x67) => 0x00,
+                    (false, 0x00) => 0x67,
+                    (false, LINE_2_START) => 0x27,
+                    // Addresses past the end of a line stay in the 7 bit range.
+                    (true, a) => (a + 1) & 0x7F,
+                    (false, a) => a - 1,
+                }
+            }
+            Ram::DdRam => match (increment, self.address) {
+                (true, 0x4F) => 0x00,
+                (false, 0x00) => 0x4F,
+                (true, a) => (a + 1) & 0x7F,
+                (false, a) => a - 1,
+            },
+        } | bank;
+    }
+    /// Shift display by one, left moves characters to the left.
+    fn step_shift(&mut self, left: bool) {
+        let len = self.line_len();
+        self.shift = if left {
+            (self.shift + 1) % len
+        } else {
+            (self.shift + len - 1) % len
+        };
+    }
+}
+
+impl HD44780 for EmulatorDriver {
+    const COMMAND_DELAY: u16 = 0;
+    fn command(&mut self, byte: u8, _delay: u16) -> Result {
+        match Instruction::try_from(byte)? {
+            Instruction::SetDdRamAddr(address) => {
+                self.ram = Ram::DdRam;
+                self.address = address;
+            }
+            Instruction::SetCgRamAddr(address) => {
+                self.ram = Ram::CgRam;
+                self.address = address;
+            }
+            Instruction::FunctionSet(mode) => {
+                self.function = mode;
+                self.shift %= self.line_len();
+            }
+            Instruction::CursorShift(mode) => {
+                let right = mode.contains(ShiftMode::MOVE_RIGHT);
+                if mode.contains(ShiftMode::DISPLAY_MOVE) {
+                    // Display moving right means the window moves left.
+                    self.step_shift(!right);
+                } else {
+                    self.step_address(right);
+                }
+            }
+            Instruction::DisplayControl(mode) => self.display = mode,
+            Instruction::EntryModeSet(mode) => self.entry = mode,
+            Instruction::ReturnHome => {
+                self.ram = Ram::DdRam;
+                self.address = 0;
+                self.shift = 0;
+            }
+            Instruction::ClearDisplay => {
+                self.dd_ram = [b' '; 256];
+                self.ram = Ram::DdRam;
+                self.address = 0;
+                self.shift = 0;
+                // Clear display always sets increment mode.
+                self.entry.insert(EntryMode::ENTRY_LEFT);
+            }
+        }
+        Ok(())
+    }
+    fn set_dd_ram_addr(&mut self, address: u8) -> Result {
+        self.instruction(Instruction::SetDdRamAddr(address))?;
+        // Keep the bank bit which is not part of the command byte.
+        self.address |= address & 0x80;
+        Ok(())
+    }
+}
+
+impl ReadableHD44780 for EmulatorDriver {
+    fn read_address_counter(&mut self) -> Result<u8> {
+        Ok(self.address & 0x7F)
+    }
+    fn read_data(&mut self, len: usize) -> Result<Vec<u8>> {
+        let increment = self.entry.contains(EntryMode::ENTRY_LEFT);
+        let mut data = Vec::with_capacity(len);
+        for _ in 0..len {
+            data.push(match self.ram {
+                Ram::CgRam => self.cg_ram[self.address as usize],
+                Ram::DdRam => self.dd_ram[self.address as usize],
+            });
+            self.step_address(increment);
+        }
+        Ok(data)
+    }
+}
+
+impl Write for EmulatorDriver {
+    fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
+        let increment = self.entry.contains(EntryMode::ENTRY_LEFT);
+        let shift = self.entry.contains(EntryMode::ENTRY_SHIFT_DISPLAY);
+        for byte in buf {
+            match self.ram {
+                Ram::CgRam => self.cg_ram[self.address as usize] = *byte,
+                Ram::DdRam => {
+                    self.dd_ram[self.address as usize] = *byte;
+                    if shift {
+                        self.step_shift(increment);
+                    }
+                }
+            }
+            self.step_address(increment);
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> IOResult<()> {
+        Ok(())
+    }
+}
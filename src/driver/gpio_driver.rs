@@ -24,13 +24,16 @@
 //! This is a very simple blocking bit-bang way of doing things which is
 //! commonly used with many micro-controllers.
 
-use crate::cmd::HD44780;
-use crate::error::HdError::{InvalidDataBusLen, SetOutputPin};
+use crate::cmd::{init_sequence, ReadableHD44780, HD44780};
+use crate::error::HdError::{BusyTimeout, InvalidDataBusLen, ReadInputPin, SetOutputPin};
 use crate::{DisplayMode, EntryMode, FunctionMode, Result};
 use embedded_hal::blocking::delay::DelayUs;
-use embedded_hal::digital::v2::OutputPin;
+use embedded_hal::digital::v2::{InputPin, OutputPin};
 use std::io::Write;
 
+/// Time(µs) to wait between busy flag reads.
+const BUSY_POLL_DELAY: u16 = 10;
+
 /// This is the driver used for direct GPIO pin connected HD44780 displays.
 ///
 /// The HD44780 display normally has a 16 inline connector.
@@ -42,17 +45,25 @@ use std::io::Write;
 ///
 /// ## Remarks
 ///
-/// This driver assumes that the `RW` input on the display is pulled to `GND`
-/// forcing the display into `Write` mode at all times.
+/// When created with [new()] this driver assumes that the `RW` input on the
+/// display is pulled to `GND` forcing the display into `Write` mode at all
+/// times.
+/// Fixed delays are then used after each command to give the display time to
+/// process it.
+///
+/// When created with [with_rw()] the driver instead reads the busy flag from
+/// the display after each command and data write and continues as soon as the
+/// display is ready.
 ///
 /// The driver can be switched between 4 and 8 bit (pin) interface by just
 /// changing the number of pins given in `data` parameter to the [new()]
 /// function when creating a new instance.
 ///
 /// [new()]: #method.new
+/// [with_rw()]: #method.with_rw
 ///
 #[derive(Debug)]
-pub struct GpioDriver<RS, EN, DP, D>
+pub struct GpioDriver<RS, EN, DP, D, RW = NoPin>
 where
     RS: OutputPin,
     EN: OutputPin,
@@ -63,6 +74,9 @@ where
     e: EN,
     data: Vec<DP>,
     delay: D,
+    rw: RW,
+    busy_timeout: u32,
+    polling: bool,
 }
 
 impl<RS, EN, DP, D> GpioDriver<RS, EN, DP, D>
@@ -79,12 +93,12 @@ where
     /// ## Arguments
     ///
     /// * `rs` - An already setup output GPIO pin that is connected to the
-    /// register select input on display.
+    ///   register select input on display.
     /// * `e` - An already setup output GPIO pin that is connected to the
-    /// enable input on display.
+    ///   enable input on display.
     /// * `data` - An already setup array or Vec of GPIO output pins that are
-    /// connected to the data inputs of the display. Only 4 or 8 pins should be
-    /// used.
+    ///   connected to the data inputs of the display. Only 4 or 8 pins should
+    ///   be used.
     ///
     /// ## Examples
     /// For examples of using the driver in both 4 and 8 bit modes have look at
@@ -100,73 +114,179 @@ where
             // data: data.into(),
             data,
             delay,
+            rw: NoPin,
+            busy_timeout: 0,
+            polling: false,
         }
     }
-    fn enable_bit_toggle(&mut self) -> Result {
-        self.e.set_low().map_err(|_| SetOutputPin("enable"))?;
-        // Give other pins some setup time before `en` toggle.
-        self.delay.delay_us(1u16);
+}
+
+impl<RS, EN, DP, D, RW> GpioDriver<RS, EN, DP, D, RW>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    DP: BidirectionalPin,
+    D: DelayUs<u16>,
+    RW: OutputPin,
+{
+    /// Create a new instance of driver that reads the display busy flag.
+    ///
+    /// ## Arguments
+    ///
+    /// * `rs` - An already setup output GPIO pin that is connected to the
+    ///   register select input on display.
+    /// * `rw` - An already setup output GPIO pin, set low, that is connected to
+    ///   the read/write input on display.
+    /// * `e` - An already setup output GPIO pin that is connected to the
+    ///   enable input on display.
+    /// * `data` - An already setup array or Vec of GPIO pins that are
+    ///   connected to the data inputs of the display and can be switched
+    ///   between output and input, see [BidirectionalPin]. Only 4 or 8 pins
+    ///   should be used.
+    /// * `delay` - Used for enable timing and between busy flag reads.
+    ///
+    /// [BidirectionalPin]: trait.BidirectionalPin.html
+    ///
+    pub fn with_rw(
+        rs: RS,
+        rw: RW,
+        e: EN,
+        data: Vec<DP>,
+        delay: D,
+    ) -> GpioDriver<RS, EN, DP, D, RW> {
+        GpioDriver {
+            rs,
+            e,
+            data,
+            delay,
+            rw,
+            busy_timeout: Self::COMMAND_DELAY as u32 * 100,
+            polling: true,
+        }
+    }
+    /// Set the longest time(µs) to wait for the display to become ready.
+    ///
+    /// Defaults to 100 times the command delay which is more than double the
+    /// time needed by the slowest commands.
+    pub fn set_busy_timeout(&mut self, timeout: u32) {
+        self.busy_timeout = timeout;
+    }
+    fn set_bus_direction(&mut self, input: bool) -> Result {
+        for pin in self.data.iter_mut() {
+            if input {
+                pin.set_input()?;
+            } else {
+                pin.set_output()?;
+            }
+        }
+        Ok(())
+    }
+    fn read_bus_bits(bus: &[DP]) -> Result<u8> {
+        let mut byte = 0u8;
+        for (i, pin) in bus.iter().enumerate() {
+            if pin.is_high().map_err(|_| ReadInputPin("data"))? {
+                byte |= 1 << i;
+            }
+        }
+        Ok(byte)
+    }
+    fn read_strobe(&mut self) -> Result<u8> {
         self.e.set_high().map_err(|_| SetOutputPin("enable"))?;
-        // Minimum time is approximately 1µs.
+        // Data is valid less than 1µs after `en` goes high.
         self.delay.delay_us(1u16);
+        let bits = Self::read_bus_bits(&self.data[..]);
         self.e.set_low().map_err(|_| SetOutputPin("enable"))?;
-        // Given a little time to ensure low state is seen.
         self.delay.delay_us(1u16);
-        Ok(())
+        bits
     }
-    fn set_control_bits(&mut self, ctrl: RegisterSelect) -> Result {
-        match ctrl {
-            RegisterSelect::Cmnd => {
-                self.rs
-                    .set_low()
-                    .map_err(|_| SetOutputPin("register select"))?;
-            }
-            RegisterSelect::Data => {
-                self.rs
-                    .set_high()
-                    .map_err(|_| SetOutputPin("register select"))?;
-            }
-        }
-        Ok(())
+    /// Reads a byte from the display register selected by `ctrl`.
+    ///
+    /// Reading the command register returns the busy flag in bit 7 and the
+    /// address counter in the lower bits.
+    fn read_byte(&mut self, ctrl: RegisterSelect) -> Result<u8> {
+        self.set_control_bits(ctrl)?;
+        self.set_bus_direction(true)?;
+        self.rw.set_high().map_err(|_| SetOutputPin("read/write"))?;
+        // Give other pins some setup time before `en` toggle.
+        self.delay.delay_us(1u16);
+        let result = match self.data.len() {
+            4 => self
+                .read_strobe()
+                .and_then(|high| Ok(high << 4 | self.read_strobe()?)),
+            8 => self.read_strobe(),
+            _ => Err(InvalidDataBusLen),
+        };
+        self.rw.set_low().map_err(|_| SetOutputPin("read/write"))?;
+        self.set_bus_direction(false)?;
+        self.set_control_bits(RegisterSelect::Data)?;
+        result
     }
-    fn set_bus_bits(byte: u8, bus: &mut [DP]) -> Result {
-        let mut mask = 0b0000_00001;
-        let mut bit: u8;
-        for pin in bus {
-            bit = byte & mask;
-            if bit == mask {
-                pin.set_high().map_err(|_| SetOutputPin("data"))?;
-            } else {
-                pin.set_low().map_err(|_| SetOutputPin("data"))?;
+    fn poll_busy_flag(&mut self) -> Result {
+        let mut waited = 0u32;
+        while self.read_byte(RegisterSelect::Cmnd)? & 0b1000_0000 != 0 {
+            if waited >= self.busy_timeout {
+                return Err(BusyTimeout(self.busy_timeout));
             }
-            mask <<= 1;
+            self.delay.delay_us(BUSY_POLL_DELAY);
+            waited += BUSY_POLL_DELAY as u32;
         }
         Ok(())
     }
+}
+
+impl<RS, EN, DP, D, RW> GpioDriver<RS, EN, DP, D, RW>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    DP: OutputPin,
+    D: DelayUs<u16>,
+{
+    fn set_control_bits(&mut self, ctrl: RegisterSelect) -> Result {
+        set_control_bits(&mut self.rs, ctrl)
+    }
     fn write_byte(&mut self, byte: u8) -> Result {
-        match self.data.len() {
-            4 => {
-                let nibble = (byte & 0b1111_0000u8) >> 4;
-                Self::set_bus_bits(nibble, &mut self.data[..])?;
-                self.enable_bit_toggle()?;
-            }
-            8 => {
-                // Nothing special needs to be done for 8 bit bus.
-            }
-            _ => return Err(InvalidDataBusLen.into()),
+        write_bus_byte(byte, &mut self.data[..], &mut self.e, &mut self.delay)
+    }
+}
+
+impl<RS, EN, DP, D> private::WaitReady for GpioDriver<RS, EN, DP, D, NoPin>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    DP: OutputPin,
+    D: DelayUs<u16>,
+{
+    fn wait_ready(&mut self, delay: u16) -> Result {
+        self.delay.delay_us(delay);
+        Ok(())
+    }
+}
+
+impl<RS, EN, DP, D, RW> private::WaitReady for GpioDriver<RS, EN, DP, D, RW>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    DP: BidirectionalPin,
+    D: DelayUs<u16>,
+    RW: OutputPin,
+{
+    fn wait_ready(&mut self, delay: u16) -> Result {
+        if self.polling {
+            self.poll_busy_flag()
+        } else {
+            self.delay.delay_us(delay);
+            Ok(())
         }
-        // Write lower nibble or full byte as needed.
-        Self::set_bus_bits(byte, &mut self.data[..])?;
-        self.enable_bit_toggle()
     }
 }
 
-impl<RS, EN, DP, D> HD44780 for GpioDriver<RS, EN, DP, D>
+impl<RS, EN, DP, D, RW> HD44780 for GpioDriver<RS, EN, DP, D, RW>
 where
     RS: OutputPin,
     EN: OutputPin,
     DP: OutputPin,
     D: DelayUs<u16>,
+    Self: private::WaitReady,
 {
     const COMMAND_DELAY: u16 = 41;
     fn command(&mut self, byte: u8, delay: u16) -> Result {
@@ -175,7 +295,7 @@ where
         // Send command.
         self.write_byte(byte)?;
         // Given HD44780 time to process command before sending anything else.
-        self.delay.delay_us(delay);
+        private::WaitReady::wait_ready(self, delay)?;
         // Switch back to data mode.
         self.set_control_bits(RegisterSelect::Data)?;
         Ok(())
@@ -189,59 +309,43 @@ where
         let fs = fs_mode.into().unwrap_or_default();
         let dc = dc_mode.into().unwrap_or_default();
         let ems = ems_mode.into().unwrap_or_default();
+        self.prepare_init()?;
+        let bus_len = self.bus_len();
+        // The busy flag can't be read until the bus width is known so fixed
+        // delays are used for the whole sequence.
+        let polling = self.polling;
+        self.polling = false;
+        let result = init_sequence(self, bus_len, fs, dc, ems);
+        self.polling = polling;
+        result
+    }
+    fn bus_len(&self) -> usize {
+        self.data.len()
+    }
+    fn prepare_init(&mut self) -> Result {
         // Insure display has had time to stabilize if just powered on.
         // This takes between 15 to 40ms depending on supplied voltage.
         // 1000 times the command delay should be enough.
-        let mut delay = Self::COMMAND_DELAY * 1000;
+        let delay = Self::COMMAND_DELAY * 1000;
         self.delay.delay_us(delay);
-        // The display can be in any of 3 states at this point and the follow
-        // sequence of commands should get it into a known and usable state.
-        // ## Phase 1 ##
-        let mut cmd = 0x33u8;
-        // Wait at least 4.1ms before issuing next instruction.
-        // 100 times command delay should work.
-        delay = Self::COMMAND_DELAY * 100;
-        self.command(cmd, delay)?;
-        // ## Phase 2 ##
-        match self.data.len() {
-            4 => {
-                if fs.contains(FunctionMode::BITS_8) {
-                    return Err(InvalidDataBusLen);
-                }
-                cmd = 0x32;
-            }
-            8 => {
-                cmd = 0x33;
-            }
-            _ => {
-                return Err(InvalidDataBusLen);
-            }
-        }
-        // Wait at least 100us before sending last special initialization command.
-        // 3 times command delay should work.
-        delay = Self::COMMAND_DELAY * 3;
-        self.command(cmd, delay)?;
-        // ## Final Phase ##
-        // Now the display is in a know state and the additional regular
-        // commands can be sent to it.
-        self.function_set(fs)?;
-        self.display_control(dc)?;
-        self.entry_mode_set(ems)?;
-        self.clear_display()?;
         Ok(())
     }
 }
 
-impl<RS, EN, DP, D> Write for GpioDriver<RS, EN, DP, D>
+impl<RS, EN, DP, D, RW> Write for GpioDriver<RS, EN, DP, D, RW>
 where
     RS: OutputPin,
     EN: OutputPin,
     DP: OutputPin,
     D: DelayUs<u16>,
+    Self: private::WaitReady,
 {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         for byte in buf {
             self.write_byte(*byte)?;
+            // Data writes have always relied on the pins being slower than
+            // the display so only wait when the busy flag can be read.
+            private::WaitReady::wait_ready(self, 0)?;
         }
         Ok(buf.len())
     }
@@ -250,16 +354,196 @@ where
     }
 }
 
-/// Use
+/// Used to select which HD44780 register (command or data) is being written.
 #[repr(u8)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub enum RegisterSelect {
     Cmnd = 0u8,
+    #[default]
     Data = 1u8,
 }
 
-impl Default for RegisterSelect {
-    fn default() -> Self {
-        RegisterSelect::Data
+impl<RS, EN, DP, D, RW> ReadableHD44780 for GpioDriver<RS, EN, DP, D, RW>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    DP: BidirectionalPin,
+    D: DelayUs<u16>,
+    RW: OutputPin,
+{
+    fn read_address_counter(&mut self) -> Result<u8> {
+        self.poll_busy_flag()?;
+        Ok(self.read_byte(RegisterSelect::Cmnd)? & 0b0111_1111)
+    }
+    fn read_data(&mut self, len: usize) -> Result<Vec<u8>> {
+        let mut buf = Vec::with_capacity(len);
+        for _ in 0..len {
+            buf.push(self.read_byte(RegisterSelect::Data)?);
+            // Address counter is updated after each read.
+            self.poll_busy_flag()?;
+        }
+        Ok(buf)
+    }
+}
+
+/// Strobes the enable pin so the display reads what is on the data bus.
+pub(crate) fn enable_bit_toggle<EN, D>(e: &mut EN, delay: &mut D) -> Result
+where
+    EN: OutputPin,
+    D: DelayUs<u16>,
+{
+    e.set_low().map_err(|_| SetOutputPin("enable"))?;
+    // Give other pins some setup time before `en` toggle.
+    delay.delay_us(1u16);
+    e.set_high().map_err(|_| SetOutputPin("enable"))?;
+    // Minimum time is approximately 1µs.
+    delay.delay_us(1u16);
+    e.set_low().map_err(|_| SetOutputPin("enable"))?;
+    // Given a little time to ensure low state is seen.
+    delay.delay_us(1u16);
+    Ok(())
+}
+
+/// Sets the register select pin for a command or data write.
+pub(crate) fn set_control_bits<RS>(rs: &mut RS, ctrl: RegisterSelect) -> Result
+where
+    RS: OutputPin,
+{
+    match ctrl {
+        RegisterSelect::Cmnd => {
+            rs.set_low().map_err(|_| SetOutputPin("register select"))?;
+        }
+        RegisterSelect::Data => {
+            rs.set_high().map_err(|_| SetOutputPin("register select"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Puts the lower bits of `byte` on the data bus pins.
+pub(crate) fn set_bus_bits<DP>(byte: u8, bus: &mut [DP]) -> Result
+where
+    DP: OutputPin,
+{
+    let mut mask = 0b0000_00001;
+    let mut bit: u8;
+    for pin in bus {
+        bit = byte & mask;
+        if bit == mask {
+            pin.set_high().map_err(|_| SetOutputPin("data"))?;
+        } else {
+            pin.set_low().map_err(|_| SetOutputPin("data"))?;
+        }
+        mask <<= 1;
+    }
+    Ok(())
+}
+
+/// Writes a byte over a 4 or 8 bit data bus strobing the given enable pin.
+pub(crate) fn write_bus_byte<DP, EN, D>(
+    byte: u8,
+    data: &mut [DP],
+    e: &mut EN,
+    delay: &mut D,
+) -> Result
+where
+    DP: OutputPin,
+    EN: OutputPin,
+    D: DelayUs<u16>,
+{
+    match data.len() {
+        4 => {
+            let nibble = (byte & 0b1111_0000u8) >> 4;
+            set_bus_bits(nibble, data)?;
+            enable_bit_toggle(e, delay)?;
+        }
+        8 => {
+            // Nothing special needs to be done for 8 bit bus.
+        }
+        _ => return Err(InvalidDataBusLen),
+    }
+    // Write lower nibble or full byte as needed.
+    set_bus_bits(byte, data)?;
+    enable_bit_toggle(e, delay)
+}
+
+/// Used in place of a `RW` pin when the display input is pulled to `GND`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NoPin;
+
+/// A GPIO pin that can be switched between output and input.
+///
+/// Needed for the data bus pins when the busy flag or other data is read back
+/// from the display.
+///
+/// ## Remarks
+///
+/// No HAL pin types implement this trait so a pin from another crate has to
+/// be wrapped in a new type which forwards [InputPin] and [OutputPin] to it
+/// and adds the direction changes.
+///
+/// ## Examples
+/// For a `linux-embedded-hal` sysfs pin:
+/// ```edition2018,ignore
+/// use embedded_hal::digital::v2::{InputPin, OutputPin};
+/// use hd44780_ntb::{BidirectionalPin, HdError};
+/// use linux_embedded_hal::sysfs_gpio::{Direction, Error};
+/// use linux_embedded_hal::Pin;
+///
+/// struct DataPin(Pin);
+///
+/// impl OutputPin for DataPin {
+///     type Error = Error;
+///     fn set_low(&mut self) -> Result<(), Error> {
+///         self.0.set_low()
+///     }
+///     fn set_high(&mut self) -> Result<(), Error> {
+///         self.0.set_high()
+///     }
+/// }
+///
+/// impl InputPin for DataPin {
+///     type Error = Error;
+///     fn is_high(&self) -> Result<bool, Error> {
+///         self.0.is_high()
+///     }
+///     fn is_low(&self) -> Result<bool, Error> {
+///         self.0.is_low()
+///     }
+/// }
+///
+/// impl BidirectionalPin for DataPin {
+///     fn set_input(&mut self) -> hd44780_ntb::Result {
+///         self.0
+///             .set_direction(Direction::In)
+///             .map_err(|_| HdError::SetPinDirection("input"))
+///     }
+///     fn set_output(&mut self) -> hd44780_ntb::Result {
+///         self.0
+///             .set_direction(Direction::Out)
+///             .map_err(|_| HdError::SetPinDirection("output"))
+///     }
+/// }
+///
+/// let data = vec![DataPin(Pin::new(25)), DataPin(Pin::new(24))/* ... */];
+/// let lcd = GpioDriver::with_rw(rs, rw, e, data, Delay);
+/// ```
+///
+/// [InputPin]: https://docs.rs/embedded-hal/0.2/embedded_hal/digital/v2/trait.InputPin.html
+/// [OutputPin]: https://docs.rs/embedded-hal/0.2/embedded_hal/digital/v2/trait.OutputPin.html
+///
+pub trait BidirectionalPin: InputPin + OutputPin {
+    /// Switch the pin to input so the display can drive it.
+    fn set_input(&mut self) -> Result;
+    /// Switch the pin back to output.
+    fn set_output(&mut self) -> Result;
+}
+
+mod private {
+    use crate::Result;
+
+    /// Waits for the display to finish processing the last command or data.
+    pub trait WaitReady {
+        fn wait_ready(&mut self, delay: u16) -> Result;
     }
 }
@@ -0,0 +1,350 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Generic blocking driver for displays connected through a MCP23008 or
+//! MCP23017 I²C port expander.
+//!
+//! This covers both the Adafruit I²C/SPI backpack in I²C mode (MCP23008) and
+//! the Adafruit RGB LCD + keypad plate (MCP23017) which adds a RGB backlight and
+//! five buttons on the same expander.
+
+use crate::cmd::HD44780;
+use crate::driver::gpio_driver::RegisterSelect;
+use crate::driver::pin_map::PinMap;
+use crate::error::HdError::{I2cRead, I2cWrite, Unsupported};
+use crate::Result;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::i2c;
+use std::io::Write;
+
+/// Default I²C address of a MCP23008 or MCP23017 with all address pins low.
+pub const MCP230XX_ADDRESS: u8 = 0x20;
+
+// Port A bits used for the red and green LEDs and port B bit used for blue on
+// the RGB plate. All are active low.
+const PLATE_RGB: [u8; 3] = [6, 7, 8];
+// Port A bits used by the buttons on the RGB plate, all active low.
+const PLATE_BUTTONS: u16 = 0b0001_1111;
+// IOCON sequential operation disable bit.
+const IOCON_SEQOP: u8 = 0b0010_0000;
+
+/// Selects which of the MCP230xx expanders is being used.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Mcp230xx {
+    /// 8 bit expander with a single port.
+    Mcp23008,
+    /// 16 bit expander with ports A (bits 0-7) and B (bits 8-15).
+    Mcp23017,
+}
+
+impl Mcp230xx {
+    /// Register addresses for IODIR, IOCON, GPPU, GPIO, and OLAT.
+    ///
+    /// MCP23017 values are for the power on default `IOCON.BANK = 0` layout.
+    fn registers(self) -> [u8; 5] {
+        match self {
+            Mcp230xx::Mcp23008 => [0x00, 0x05, 0x06, 0x09, 0x0A],
+            Mcp230xx::Mcp23017 => [0x00, 0x0A, 0x0C, 0x12, 0x14],
+        }
+    }
+}
+
+bitflags! {
+    /// Buttons on the Adafruit RGB LCD + keypad plate.
+    ///
+    /// Returned from [buttons()] with a bit set for each button being pressed.
+    ///
+    /// [buttons()]: struct.I2cMcp230xxDriver.html#method.buttons
+    ///
+    #[derive(Default)]
+    pub struct Buttons: u8 {
+        const SELECT = 0x01;
+        const RIGHT = 0x02;
+        const DOWN = 0x04;
+        const UP = 0x08;
+        const LEFT = 0x10;
+    }
+}
+
+/// This is the driver used for HD44780 displays behind a MCP23008 or MCP23017
+/// I²C expander.
+///
+/// ## Remarks
+///
+/// The expander is switched into byte mode (`IOCON.SEQOP`) during [init()] so
+/// that each byte sent to the display, including all of the enable strobes, is
+/// done with a single I²C write to the output latch register.
+/// [init()] must be called before anything else as it is also where the
+/// expander pins are set up as outputs and inputs.
+///
+/// [init()]: #method.init
+///
+#[derive(Debug)]
+pub struct I2cMcp230xxDriver<I2C, D>
+where
+    I2C: i2c::Write + i2c::WriteRead,
+    D: DelayUs<u16>,
+{
+    i2c: I2C,
+    address: u8,
+    chip: Mcp230xx,
+    pins: PinMap,
+    plate: bool,
+    backlight: bool,
+    rgb: u16,
+    rs: RegisterSelect,
+    delay: D,
+}
+
+impl<I2C, D> I2cMcp230xxDriver<I2C, D>
+where
+    I2C: i2c::Write + i2c::WriteRead,
+    D: DelayUs<u16>,
+{
+    /// Create a new instance of driver for any expander wiring.
+    ///
+    /// ## Arguments
+    ///
+    /// * `i2c` - An already setup I²C bus the expander is connected to.
+    /// * `address` - The I²C address of the expander.
+    /// * `chip` - Which of the expanders is being used.
+    /// * `pins` - Which expander output bits are connected to which display
+    ///   pins. Bit numbers 8 to 15 are port B on the MCP23017.
+    /// * `delay` - Used to give the display time to process commands.
+    ///
+    /// ## Errors
+    /// Returns [InvalidPinMap] if any of the bit numbers are not outputs of
+    /// the chip.
+    ///
+    /// [InvalidPinMap]: enum.HdError.html#variant.InvalidPinMap
+    ///
+    pub fn new(i2c: I2C, address: u8, chip: Mcp230xx, pins: PinMap, delay: D) -> Result<Self> {
+        let outputs = match chip {
+            Mcp230xx::Mcp23008 => 8,
+            Mcp230xx::Mcp23017 => 16,
+        };
+        pins.check(outputs)?;
+        Ok(Self::from_pins(i2c, address, chip, pins, delay))
+    }
+    fn from_pins(i2c: I2C, address: u8, chip: Mcp230xx, pins: PinMap, delay: D) -> Self {
+        I2cMcp230xxDriver {
+            i2c,
+            address,
+            chip,
+            pins,
+            plate: false,
+            backlight: true,
+            rgb: 0,
+            rs: RegisterSelect::Data,
+            delay,
+        }
+    }
+    /// Create a new instance of driver for the Adafruit I²C/SPI backpack.
+    ///
+    /// Same as calling [new()] with [Mcp230xx::Mcp23008] and [PinMap::MCP23008].
+    ///
+    /// [new()]: #method.new
+    /// [Mcp230xx::Mcp23008]: enum.Mcp230xx.html#variant.Mcp23008
+    /// [PinMap::MCP23008]: struct.PinMap.html#associatedconstant.MCP23008
+    ///
+    pub fn backpack(i2c: I2C, address: u8, delay: D) -> Self {
+        Self::from_pins(i2c, address, Mcp230xx::Mcp23008, PinMap::MCP23008, delay)
+    }
+    /// Create a new instance of driver for the Adafruit RGB LCD + keypad plate.
+    ///
+    /// Enables [set_rgb()] and [buttons()] in addition to the display commands.
+    /// The backlight starts out white (all LEDs on).
+    ///
+    /// [set_rgb()]: #method.set_rgb
+    /// [buttons()]: #method.buttons
+    ///
+    pub fn rgb_plate(i2c: I2C, address: u8, delay: D) -> Self {
+        let mut driver =
+            Self::from_pins(i2c, address, Mcp230xx::Mcp23017, PinMap::RGB_PLATE, delay);
+        driver.plate = true;
+        driver
+    }
+    /// Returns `true` when the backlight is on.
+    pub fn backlight(&self) -> bool {
+        self.backlight
+    }
+    /// Turn the backlight on or off.
+    ///
+    /// On the RGB plate this turns all of the LEDs on or off together.
+    pub fn set_backlight(&mut self, on: bool) -> Result {
+        self.backlight = on;
+        if self.plate {
+            self.rgb = if on {
+                0
+            } else {
+                Self::rgb_bits(true, true, true)
+            };
+        }
+        self.write_latch(&[self.idle_frame()])
+    }
+    /// Set which of the red, green, and blue backlight LEDs are on.
+    ///
+    /// ## Errors
+    /// Returns an error if the driver wasn't created with [rgb_plate()].
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// // Yellow
+    /// lcd.set_rgb(true, true, false)?;
+    /// ```
+    ///
+    /// [rgb_plate()]: #method.rgb_plate
+    ///
+    pub fn set_rgb(&mut self, red: bool, green: bool, blue: bool) -> Result {
+        if !self.plate {
+            return Err(Unsupported("RGB backlight"));
+        }
+        // LEDs are on when their outputs are low.
+        self.rgb = Self::rgb_bits(!red, !green, !blue);
+        self.backlight = red || green || blue;
+        self.write_latch(&[self.idle_frame()])
+    }
+    /// Read which of the plate buttons are currently being pressed.
+    ///
+    /// ## Errors
+    /// Returns an error if the driver wasn't created with [rgb_plate()].
+    ///
+    /// [rgb_plate()]: #method.rgb_plate
+    ///
+    pub fn buttons(&mut self) -> Result<Buttons> {
+        if !self.plate {
+            return Err(Unsupported("Buttons"));
+        }
+        let register = self.chip.registers()[3];
+        let mut buf = [0u8; 2];
+        let address = self.address;
+        self.i2c
+            .write_read(address, &[register], &mut buf)
+            .map_err(|_| I2cRead(address))?;
+        // Buttons pull their inputs low when pressed.
+        Ok(Buttons::from_bits_truncate(!buf[0] & PLATE_BUTTONS as u8))
+    }
+    fn rgb_bits(red: bool, green: bool, blue: bool) -> u16 {
+        let mut bits = 0;
+        for (on, bit) in [red, green, blue].iter().zip(PLATE_RGB.iter()) {
+            if *on {
+                bits |= 1 << bit;
+            }
+        }
+        bits
+    }
+    fn idle_frame(&self) -> u16 {
+        self.pins.frame(0, self.rs, self.backlight) | self.rgb
+    }
+    fn write_register(&mut self, register: u8, value: u16) -> Result {
+        let address = self.address;
+        let bytes = [register, value as u8, (value >> 8) as u8];
+        let len = match self.chip {
+            Mcp230xx::Mcp23008 => 2,
+            Mcp230xx::Mcp23017 => 3,
+        };
+        self.i2c
+            .write(address, &bytes[..len])
+            .map_err(|_| I2cWrite(address))
+    }
+    fn write_latch(&mut self, frames: &[u16]) -> Result {
+        let mut bytes = vec![self.chip.registers()[4]];
+        for frame in frames {
+            bytes.push(*frame as u8);
+            if self.chip == Mcp230xx::Mcp23017 {
+                bytes.push((*frame >> 8) as u8);
+            }
+        }
+        let address = self.address;
+        self.i2c
+            .write(address, &bytes)
+            .map_err(|_| I2cWrite(address))
+    }
+    fn write_byte(&mut self, byte: u8) -> Result {
+        let mut frames = self.pins.byte_frames(byte, self.rs, self.backlight);
+        for frame in frames.iter_mut() {
+            *frame |= self.rgb;
+        }
+        self.write_latch(&frames)
+    }
+    fn setup_expander(&mut self) -> Result {
+        let [iodir, iocon, gppu, _, _] = self.chip.registers();
+        let mut outputs = self.pins.mask();
+        let mut pull_ups = 0;
+        if self.plate {
+            outputs |= Self::rgb_bits(true, true, true);
+            pull_ups = PLATE_BUTTONS;
+        }
+        // Byte mode so the latch register can be written over and over.
+        self.write_register(iocon, IOCON_SEQOP as u16 | (IOCON_SEQOP as u16) << 8)?;
+        // Outputs are set with zeros in the direction register.
+        self.write_register(iodir, !outputs)?;
+        self.write_register(gppu, pull_ups)?;
+        self.write_latch(&[self.idle_frame()])
+    }
+}
+
+impl<I2C, D> HD44780 for I2cMcp230xxDriver<I2C, D>
+where
+    I2C: i2c::Write + i2c::WriteRead,
+    D: DelayUs<u16>,
+{
+    const COMMAND_DELAY: u16 = 41;
+    fn command(&mut self, byte: u8, delay: u16) -> Result {
+        // Switch to command mode.
+        self.rs = RegisterSelect::Cmnd;
+        // Send command.
+        let result = self.write_byte(byte);
+        // Switch back to data mode.
+        self.rs = RegisterSelect::Data;
+        result?;
+        // Given HD44780 time to process command before sending anything else.
+        self.delay.delay_us(delay);
+        Ok(())
+    }
+    fn bus_len(&self) -> usize {
+        4
+    }
+    fn prepare_init(&mut self) -> Result {
+        // Insure display has had time to stabilize if just powered on.
+        // This takes between 15 to 40ms depending on supplied voltage.
+        // 1000 times the command delay should be enough.
+        let delay = Self::COMMAND_DELAY * 1000;
+        self.delay.delay_us(delay);
+        self.setup_expander()
+    }
+}
+
+impl<I2C, D> Write for I2cMcp230xxDriver<I2C, D>
+where
+    I2C: i2c::Write + i2c::WriteRead,
+    D: DelayUs<u16>,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for byte in buf {
+            self.write_byte(*byte)?;
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Generic blocking driver for displays connected through a PCF8574 I²C
+//! port expander.
+//!
+//! Almost all of the cheap I²C "backpacks" sold with HD44780 displays are built
+//! around either a PCF8574 or PCF8574A. The expander only has 8 outputs so the
+//! display is always used with a 4 bit (pin) data bus.
+
+use crate::cmd::HD44780;
+use crate::driver::gpio_driver::RegisterSelect;
+use crate::driver::pin_map::PinMap;
+use crate::error::HdError::I2cWrite;
+use crate::Result;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::i2c;
+use std::io::Write;
+
+/// Default I²C address of a PCF8574 with all address pins pulled high.
+pub const PCF8574_ADDRESS: u8 = 0x27;
+/// Default I²C address of a PCF8574A with all address pins pulled high.
+pub const PCF8574A_ADDRESS: u8 = 0x3F;
+
+/// This is the driver used for HD44780 displays behind a PCF8574 I²C backpack.
+///
+/// ## Remarks
+///
+/// Each byte sent to the display is done as a single I²C write of four bytes
+/// to the expander, two for each nibble, the first with the enable bit high and
+/// the second with it low. At 100KHz that gives the display far more than the
+/// minimum enable pulse width.
+///
+/// The `RW` bit, if wired, is always kept low which keeps the display in
+/// `Write` mode at all times.
+///
+#[derive(Debug)]
+pub struct I2cPcf8574Driver<I2C, D>
+where
+    I2C: i2c::Write,
+    D: DelayUs<u16>,
+{
+    i2c: I2C,
+    address: u8,
+    pins: PinMap,
+    backlight: bool,
+    rs: RegisterSelect,
+    delay: D,
+}
+
+impl<I2C, D> I2cPcf8574Driver<I2C, D>
+where
+    I2C: i2c::Write,
+    D: DelayUs<u16>,
+{
+    /// Create a new instance of driver using the common backpack wiring.
+    ///
+    /// Same as calling [with_pins()] with [PinMap::PCF8574].
+    ///
+    /// ## Arguments
+    ///
+    /// * `i2c` - An already setup I²C bus the expander is connected to.
+    /// * `address` - The I²C address of the expander. Typically this will be
+    ///   [PCF8574_ADDRESS] or [PCF8574A_ADDRESS] depending on the chip used.
+    /// * `delay` - Used to give the display time to process commands.
+    ///
+    /// [with_pins()]: #method.with_pins
+    /// [PinMap::PCF8574]: struct.PinMap.html#associatedconstant.PCF8574
+    /// [PCF8574_ADDRESS]: constant.PCF8574_ADDRESS.html
+    /// [PCF8574A_ADDRESS]: constant.PCF8574A_ADDRESS.html
+    ///
+    pub fn new(i2c: I2C, address: u8, delay: D) -> I2cPcf8574Driver<I2C, D> {
+        Self::from_pins(i2c, address, PinMap::PCF8574, delay)
+    }
+    /// Create a new instance of driver for a backpack with custom wiring.
+    ///
+    /// ## Arguments
+    ///
+    /// * `i2c` - An already setup I²C bus the expander is connected to.
+    /// * `address` - The I²C address of the expander.
+    /// * `pins` - Which expander output bits are connected to which display
+    ///   pins. All bit numbers must be in the range 0 to 7.
+    /// * `delay` - Used to give the display time to process commands.
+    ///
+    /// ## Errors
+    /// Returns [InvalidPinMap] if any of the bit numbers are out of range.
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// let lcd = I2cPcf8574Driver::with_pins(i2c, 0x20, PinMap::PCF8574_MJKDZ, Delay)?;
+    /// ```
+    ///
+    /// [InvalidPinMap]: enum.HdError.html#variant.InvalidPinMap
+    ///
+    pub fn with_pins(i2c: I2C, address: u8, pins: PinMap, delay: D) -> Result<Self> {
+        pins.check(8)?;
+        Ok(Self::from_pins(i2c, address, pins, delay))
+    }
+    fn from_pins(i2c: I2C, address: u8, pins: PinMap, delay: D) -> Self {
+        I2cPcf8574Driver {
+            i2c,
+            address,
+            pins,
+            backlight: true,
+            rs: RegisterSelect::Data,
+            delay,
+        }
+    }
+    /// Returns `true` when the backlight is on.
+    pub fn backlight(&self) -> bool {
+        self.backlight
+    }
+    /// Turn the backlight on or off.
+    ///
+    /// The new state is written to the expander right away and is kept for
+    /// all following commands and data writes.
+    pub fn set_backlight(&mut self, on: bool) -> Result {
+        self.backlight = on;
+        let frame = self.pins.backlight_bits(on) as u8;
+        self.send(&[frame])
+    }
+    fn send(&mut self, frames: &[u8]) -> Result {
+        let address = self.address;
+        self.i2c
+            .write(address, frames)
+            .map_err(|_| I2cWrite(address))
+    }
+    fn write_byte(&mut self, byte: u8) -> Result {
+        let frames = self.pins.byte_frames(byte, self.rs, self.backlight);
+        let mut bytes = [0u8; 4];
+        for (b, f) in bytes.iter_mut().zip(frames.iter()) {
+            *b = *f as u8;
+        }
+        self.send(&bytes)
+    }
+}
+
+impl<I2C, D> HD44780 for I2cPcf8574Driver<I2C, D>
+where
+    I2C: i2c::Write,
+    D: DelayUs<u16>,
+{
+    const COMMAND_DELAY: u16 = 41;
+    fn command(&mut self, byte: u8, delay: u16) -> Result {
+        // Switch to command mode.
+        self.rs = RegisterSelect::Cmnd;
+        // Send command.
+        let result = self.write_byte(byte);
+        // Switch back to data mode.
+        self.rs = RegisterSelect::Data;
+        result?;
+        // Given HD44780 time to process command before sending anything else.
+        self.delay.delay_us(delay);
+        Ok(())
+    }
+    fn bus_len(&self) -> usize {
+        4
+    }
+    fn prepare_init(&mut self) -> Result {
+        // Insure display has had time to stabilize if just powered on.
+        // This takes between 15 to 40ms depending on supplied voltage.
+        // 1000 times the command delay should be enough.
+        let delay = Self::COMMAND_DELAY * 1000;
+        self.delay.delay_us(delay);
+        // Start from a known expander state with enable low.
+        let frame = self.pins.backlight_bits(self.backlight) as u8;
+        self.send(&[frame])
+    }
+}
+
+impl<I2C, D> Write for I2cPcf8574Driver<I2C, D>
+where
+    I2C: i2c::Write,
+    D: DelayUs<u16>,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for byte in buf {
+            self.write_byte(*byte)?;
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
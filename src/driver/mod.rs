@@ -21,5 +21,15 @@
 // SOFTWARE.
 //! Module for all drivers like GPIO, I2C, SPI, etc.
 
+pub mod cdev_driver;
+pub mod char_lcd_driver;
+pub mod dual_gpio_driver;
+pub mod emulator_driver;
 pub mod gpio_driver;
+pub mod i2c_mcp230xx_driver;
+pub mod i2c_pcf8574_driver;
+pub mod pin_map;
+pub mod serial_driver;
+pub mod shift_register_driver;
 pub mod spy_driver;
+pub mod terminal_driver;
@@ -0,0 +1,208 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Mapping of the display control and data lines onto the outputs of port
+//! expanders and shift registers.
+//!
+//! Vendors wire the same expander chips to the display in different ways so
+//! the drivers using them take one of these maps to know which output bit
+//! drives which display pin.
+
+use crate::driver::gpio_driver::RegisterSelect;
+use crate::error::HdError::InvalidPinMap;
+use crate::Result;
+
+/// Bit positions of the display pins on an expander or shift register output.
+///
+/// Every field is the bit number (0 is the least significant bit) of the
+/// expander output the display pin is connected to.
+/// Only 4 bit (pin) data bus connections are supported so `data` holds the
+/// bit numbers for `D4` through `D7` in that order.
+///
+/// ## Examples
+/// ```edition2018
+/// use hd44780_ntb::PinMap;
+///
+/// // Same wiring as most of the PCF8574 backpacks.
+/// let pins = PinMap {
+///     rs: 0,
+///     rw: Some(1),
+///     e: 2,
+///     data: [4, 5, 6, 7],
+///     backlight: Some(3),
+///     backlight_active_low: false,
+/// };
+/// assert_eq!(pins, PinMap::PCF8574);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PinMap {
+    /// Output bit connected to the register select input on display.
+    pub rs: u8,
+    /// Output bit connected to the read/write input on display if any.
+    ///
+    /// The drivers only write to the display so this bit is always kept low.
+    pub rw: Option<u8>,
+    /// Output bit connected to the enable input on display.
+    pub e: u8,
+    /// Output bits connected to `D4` through `D7` inputs on display.
+    pub data: [u8; 4],
+    /// Output bit that switches the backlight if any.
+    pub backlight: Option<u8>,
+    /// Set when the backlight is on while its output bit is low.
+    pub backlight_active_low: bool,
+}
+
+impl PinMap {
+    /// Wiring used by most PCF8574 and PCF8574A I²C backpacks.
+    ///
+    /// `P0` = RS, `P1` = RW, `P2` = E, `P3` = backlight, `P4`-`P7` = D4-D7.
+    pub const PCF8574: PinMap = PinMap {
+        rs: 0,
+        rw: Some(1),
+        e: 2,
+        data: [4, 5, 6, 7],
+        backlight: Some(3),
+        backlight_active_low: false,
+    };
+    /// Wiring used by the mjkdz style PCF8574 I²C backpacks.
+    ///
+    /// `P0`-`P3` = D4-D7, `P4` = E, `P5` = RW, `P6` = RS, `P7` = backlight
+    /// (active low).
+    pub const PCF8574_MJKDZ: PinMap = PinMap {
+        rs: 6,
+        rw: Some(5),
+        e: 4,
+        data: [0, 1, 2, 3],
+        backlight: Some(7),
+        backlight_active_low: true,
+    };
+    /// Wiring used by the Adafruit I²C/SPI backpack in I²C (MCP23008) mode.
+    ///
+    /// `GP1` = RS, `GP2` = E, `GP3`-`GP6` = D4-D7, `GP7` = backlight.
+    pub const MCP23008: PinMap = PinMap {
+        rs: 1,
+        rw: None,
+        e: 2,
+        data: [3, 4, 5, 6],
+        backlight: Some(7),
+        backlight_active_low: false,
+    };
+    /// Wiring used by the Adafruit RGB LCD + keypad plate (MCP23017).
+    ///
+    /// Port A is bits 0-7 and port B is bits 8-15.
+    /// `GPB7` = RS, `GPB6` = RW, `GPB5` = E, `GPB4`-`GPB1` = D4-D7.
+    /// The RGB backlight is handled separately by the driver.
+    pub const RGB_PLATE: PinMap = PinMap {
+        rs: 15,
+        rw: Some(14),
+        e: 13,
+        data: [12, 11, 10, 9],
+        backlight: None,
+        backlight_active_low: false,
+    };
+    /// Wiring used by the Adafruit I²C/SPI backpack in SPI (74HC595) mode.
+    ///
+    /// `Q1` = RS, `Q2` = E, `Q6`-`Q3` = D4-D7, `Q7` = backlight.
+    pub const SPI_BACKPACK: PinMap = PinMap {
+        rs: 1,
+        rw: None,
+        e: 2,
+        data: [6, 5, 4, 3],
+        backlight: Some(7),
+        backlight_active_low: false,
+    };
+    /// Check that every bit used is one of the outputs available.
+    ///
+    /// ## Arguments
+    /// * `outputs` - Number of output bits, 8 or 16.
+    ///
+    /// ## Errors
+    /// Returns [InvalidPinMap] with the first bit that is out of range.
+    ///
+    /// [InvalidPinMap]: enum.HdError.html#variant.InvalidPinMap
+    ///
+    pub(crate) fn check(&self, outputs: u8) -> Result {
+        let control = [self.rs, self.e];
+        let mut bits = control
+            .iter()
+            .chain(self.data.iter())
+            .chain(self.rw.iter())
+            .chain(self.backlight.iter());
+        match bits.find(|bit| **bit >= outputs) {
+            Some(bit) => Err(InvalidPinMap(*bit)),
+            None => Ok(()),
+        }
+    }
+    /// Builds the output bits for a nibble on the data bus with enable low.
+    ///
+    /// ## Arguments
+    /// * `nibble` - Lower 4 bits are put on `D4` through `D7`.
+    /// * `rs` - Which register is being written.
+    /// * `backlight` - If the backlight should be on.
+    pub(crate) fn frame(&self, nibble: u8, rs: RegisterSelect, backlight: bool) -> u16 {
+        let mut frame = self.backlight_bits(backlight);
+        if rs == RegisterSelect::Data {
+            frame |= 1 << self.rs;
+        }
+        for (i, bit) in self.data.iter().enumerate() {
+            if nibble & (1 << i) != 0 {
+                frame |= 1 << bit;
+            }
+        }
+        frame
+    }
+    /// Builds the sequence of output frames needed to send a byte over a 4 bit
+    /// data bus.
+    ///
+    /// The same high nibble then low nibble order as used by the GPIO driver
+    /// with each nibble held on the bus while enable is strobed high then low.
+    pub(crate) fn byte_frames(&self, byte: u8, rs: RegisterSelect, backlight: bool) -> [u16; 4] {
+        let high = self.frame(byte >> 4, rs, backlight);
+        let low = self.frame(byte & 0b0000_1111, rs, backlight);
+        let enable = self.enable();
+        [high | enable, high, low | enable, low]
+    }
+    /// Output bits that are connected to the display or backlight.
+    pub(crate) fn mask(&self) -> u16 {
+        let mut mask = (1 << self.rs) | (1 << self.e);
+        if let Some(bit) = self.rw {
+            mask |= 1 << bit;
+        }
+        if let Some(bit) = self.backlight {
+            mask |= 1 << bit;
+        }
+        for bit in self.data.iter() {
+            mask |= 1 << bit;
+        }
+        mask
+    }
+    /// Output bits with only the enable bit set.
+    pub(crate) fn enable(&self) -> u16 {
+        1 << self.e
+    }
+    /// Output bits needed to set the backlight on or off.
+    pub(crate) fn backlight_bits(&self, on: bool) -> u16 {
+        match self.backlight {
+            Some(bit) if on != self.backlight_active_low => 1 << bit,
+            _ => 0,
+        }
+    }
+}
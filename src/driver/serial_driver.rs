@@ -0,0 +1,327 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Driver for UART attached serial LCD backpacks.
+//!
+//! Supports both the SparkFun SerLCD (OpenLCD firmware) and Matrix Orbital
+//! command sets so displays using either can be driven through the same
+//! HD44780 command set as directly connected ones.
+
+use crate::cmd::HD44780;
+use crate::error::HdError::Unsupported;
+use crate::{DisplayMode, EntryMode, FunctionMode, Instruction, Result, ShiftMode};
+use std::convert::TryFrom;
+use std::io::Write;
+
+// SerLCD prefixes.
+const SERLCD_COMMAND: u8 = 0xFE;
+const SERLCD_SETTING: u8 = 0x7C;
+// Matrix Orbital command prefix.
+const MO_COMMAND: u8 = 0xFE;
+
+/// Serial command set spoken by the display backpack.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SerialProtocol {
+    /// SparkFun SerLCD with the OpenLCD firmware.
+    ///
+    /// Most HD44780 commands are passed straight through to the display.
+    SerLcd,
+    /// Matrix Orbital LCD and LK series command set.
+    ///
+    /// The number of `columns` on the display is needed to convert DD RAM
+    /// addresses into the column and row positions used by the protocol.
+    MatrixOrbital {
+        /// Number of characters per row on the display.
+        columns: u8,
+    },
+}
+
+/// This is the driver used for HD44780 displays behind a serial backpack.
+///
+/// ## Remarks
+///
+/// The backpack takes care of the display bus and initialization so
+/// [function_set()] is ignored and [init()] only sets the display and entry
+/// modes and clears the display.
+///
+/// CG RAM data is collected and each changed custom character is uploaded as a
+/// whole using the protocol's custom character command at the end of each
+/// write.
+///
+/// With [SerialProtocol::SerLcd] the `|` (`0x7C`) and `0xFE` bytes can __NOT__
+/// be displayed as they are the protocol command prefixes and custom
+/// characters `0x00`-`0x0F` are sent using the OpenLCD display custom
+/// character command.
+///
+/// The Matrix Orbital protocol has no display shift or entry modes other than
+/// left to right so those return an error. Its `0xFE` command prefix can
+/// __NOT__ be displayed either. Prefix bytes in text are sent as spaces with
+/// both protocols so they can't start a command by mistake.
+///
+/// The Matrix Orbital protocol also has no way to blank the text so turning
+/// the display off with [display_control()] only turns the backlight off and
+/// the text stays visible.
+///
+/// [function_set()]: trait.HD44780.html#method.function_set
+/// [init()]: #method.init
+/// [display_control()]: trait.HD44780.html#method.display_control
+/// [SerialProtocol::SerLcd]: enum.SerialProtocol.html#variant.SerLcd
+///
+#[derive(Debug)]
+pub struct SerialDriver<W>
+where
+    W: Write,
+{
+    port: W,
+    protocol: SerialProtocol,
+    cg_ram: [u8; 64],
+    cg_address: Option<u8>,
+    dirty: u8,
+}
+
+impl<W> SerialDriver<W>
+where
+    W: Write,
+{
+    /// Create a new instance of driver.
+    ///
+    /// ## Arguments
+    ///
+    /// * `port` - An already opened and configured serial port (or anything
+    ///   else that can be written to).
+    /// * `protocol` - The command set the display backpack uses.
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// let port = OpenOptions::new().write(true).open("/dev/ttyUSB0")?;
+    /// let mut lcd = SerialDriver::new(port, SerialProtocol::SerLcd);
+    /// ```
+    pub fn new(port: W, protocol: SerialProtocol) -> SerialDriver<W> {
+        SerialDriver {
+            port,
+            protocol,
+            cg_ram: [0; 64],
+            cg_address: None,
+            dirty: 0,
+        }
+    }
+    /// Get a reference to the underlying serial port.
+    pub fn get_ref(&self) -> &W {
+        &self.port
+    }
+    /// Turn the backlight fully on or off.
+    pub fn set_backlight(&mut self, on: bool) -> Result {
+        match self.protocol {
+            // Primary backlight brightness from 128 (off) to 157 (full).
+            SerialProtocol::SerLcd => self.send(&[SERLCD_SETTING, if on { 157 } else { 128 }]),
+            // Backlight on with 0 minutes meaning stay on.
+            SerialProtocol::MatrixOrbital { .. } => {
+                if on {
+                    self.send(&[MO_COMMAND, b'B', 0])
+                } else {
+                    self.send(&[MO_COMMAND, b'F'])
+                }
+            }
+        }
+    }
+    fn send(&mut self, bytes: &[u8]) -> Result {
+        self.port.write_all(bytes)?;
+        Ok(())
+    }
+    fn flush_cg_ram(&mut self) -> Result {
+        for c in 0..8u8 {
+            if self.dirty & (1 << c) == 0 {
+                continue;
+            }
+            let mut bytes = match self.protocol {
+                SerialProtocol::SerLcd => vec![SERLCD_SETTING, 27 + c],
+                SerialProtocol::MatrixOrbital { .. } => vec![MO_COMMAND, b'N', c],
+            };
+            let start = c as usize * 8;
+            bytes.extend_from_slice(&self.cg_ram[start..start + 8]);
+            self.send(&bytes)?;
+        }
+        self.dirty = 0;
+        Ok(())
+    }
+    fn serlcd_command(&mut self, byte: u8, instruction: Instruction) -> Result {
+        match instruction {
+            Instruction::SetCgRamAddr(address) => {
+                // Custom characters are uploaded with their own command instead.
+                self.cg_address = Some(address);
+                return Ok(());
+            }
+            // Backpack controls the display bus so never change it.
+            Instruction::FunctionSet(_) => return Ok(()),
+            Instruction::SetDdRamAddr(_) | Instruction::ClearDisplay | Instruction::ReturnHome => {
+                self.cg_address = None;
+            }
+            _ => {}
+        }
+        self.send(&[SERLCD_COMMAND, byte])
+    }
+    fn matrix_orbital_command(&mut self, instruction: Instruction, columns: u8) -> Result {
+        match instruction {
+            Instruction::SetDdRamAddr(address) => {
+                self.cg_address = None;
+                // Convert to 1 based column and row.
+                let (mut col, mut row) = (address, 0);
+                if col >= 0x40 {
+                    col -= 0x40;
+                    row += 1;
+                }
+                if columns > 0 && col >= columns {
+                    col -= columns;
+                    row += 2;
+                }
+                self.send(&[MO_COMMAND, b'G', col + 1, row + 1])
+            }
+            Instruction::SetCgRamAddr(address) => {
+                self.cg_address = Some(address);
+                Ok(())
+            }
+            Instruction::FunctionSet(_) => Ok(()),
+            Instruction::CursorShift(mode) => {
+                if mode.contains(ShiftMode::DISPLAY_MOVE) {
+                    return Err(Unsupported("Display shift"));
+                }
+                if mode.contains(ShiftMode::MOVE_RIGHT) {
+                    self.send(&[MO_COMMAND, b'M'])
+                } else {
+                    self.send(&[MO_COMMAND, b'L'])
+                }
+            }
+            Instruction::DisplayControl(mode) => {
+                // Only the backlight can be turned off, see the remarks above.
+                if mode.contains(DisplayMode::DISPLAY_ON) {
+                    self.send(&[MO_COMMAND, b'B', 0])?;
+                } else {
+                    self.send(&[MO_COMMAND, b'F'])?;
+                }
+                let underline = if mode.contains(DisplayMode::CURSOR_ON) {
+                    b'J'
+                } else {
+                    b'K'
+                };
+                let block = if mode.contains(DisplayMode::BLINK_ON) {
+                    b'S'
+                } else {
+                    b'T'
+                };
+                self.send(&[MO_COMMAND, underline, MO_COMMAND, block])
+            }
+            Instruction::EntryModeSet(mode) => {
+                if mode != EntryMode::default() {
+                    return Err(Unsupported("Entry mode other than left to right"));
+                }
+                Ok(())
+            }
+            Instruction::ReturnHome => {
+                self.cg_address = None;
+                self.send(&[MO_COMMAND, b'H'])
+            }
+            Instruction::ClearDisplay => {
+                self.cg_address = None;
+                self.send(&[MO_COMMAND, b'X'])
+            }
+        }
+    }
+}
+
+impl<W> HD44780 for SerialDriver<W>
+where
+    W: Write,
+{
+    // At common baud rates each byte takes longer than most commands.
+    const COMMAND_DELAY: u16 = 0;
+    fn command(&mut self, byte: u8, _delay: u16) -> Result {
+        let instruction = match Instruction::try_from(byte) {
+            Ok(instruction) => instruction,
+            // Only 0x00 is left which isn't a command.
+            Err(_) => return Ok(()),
+        };
+        match self.protocol {
+            SerialProtocol::SerLcd => self.serlcd_command(byte, instruction),
+            SerialProtocol::MatrixOrbital { columns } => {
+                self.matrix_orbital_command(instruction, columns)
+            }
+        }
+    }
+    fn init<FM, DM, EM>(&mut self, fs_mode: FM, dc_mode: DM, ems_mode: EM) -> Result
+    where
+        FM: Into<Option<FunctionMode>>,
+        DM: Into<Option<DisplayMode>>,
+        EM: Into<Option<EntryMode>>,
+    {
+        let fs = fs_mode.into().unwrap_or_default();
+        let dc = dc_mode.into().unwrap_or_default();
+        let ems = ems_mode.into().unwrap_or_default();
+        // Still checked so invalid modes are caught the same as other drivers.
+        self.function_set(fs)?;
+        self.display_control(dc)?;
+        self.entry_mode_set(ems)?;
+        self.clear_display()
+    }
+}
+
+impl<W> Write for SerialDriver<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.cg_address {
+            Some(mut address) => {
+                for byte in buf {
+                    self.cg_ram[address as usize] = *byte;
+                    self.dirty |= 1 << (address / 8);
+                    address = (address + 1) & 0b0011_1111;
+                }
+                self.cg_address = Some(address);
+                self.flush_cg_ram()?;
+            }
+            None => match self.protocol {
+                SerialProtocol::SerLcd => {
+                    for byte in buf {
+                        match byte {
+                            // Display custom character command.
+                            0x00..=0x0F => {
+                                self.send(&[SERLCD_SETTING, 35 + (byte & 0b0000_0111)])?
+                            }
+                            &SERLCD_SETTING | &SERLCD_COMMAND => self.send(b" ")?,
+                            _ => self.send(&[*byte])?,
+                        }
+                    }
+                }
+                SerialProtocol::MatrixOrbital { .. } => {
+                    let text: Vec<u8> = buf
+                        .iter()
+                        .map(|&b| if b == MO_COMMAND { b' ' } else { b })
+                        .collect();
+                    self.send(&text)?
+                }
+            },
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.port.flush()
+    }
+}
@@ -0,0 +1,203 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Generic blocking driver for displays connected through a 74HC595 shift
+//! register on a SPI bus.
+//!
+//! Only 3 pins (clock, data, and latch) are needed to drive the display which
+//! is why this is used by the Adafruit I²C/SPI backpack in SPI mode and many
+//! DIY designs.
+
+use crate::cmd::HD44780;
+use crate::driver::gpio_driver::RegisterSelect;
+use crate::driver::pin_map::PinMap;
+use crate::error::HdError::{SetOutputPin, SpiWrite};
+use crate::Result;
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use std::io::Write;
+
+/// This is the driver used for HD44780 displays behind a 74HC595 shift register.
+///
+/// ## Remarks
+///
+/// The SPI bus should be set up in mode 0 with the most significant bit sent
+/// first so that bit numbers in the [PinMap] match the `Q0`-`Q7` outputs of
+/// the shift register.
+///
+/// Every change of the display pins is a separate frame that is shifted out
+/// then latched onto the outputs by a rising edge on the `latch` pin.
+/// Each nibble sent to the display takes three frames with the enable bit low,
+/// high, and then low again.
+///
+/// [PinMap]: struct.PinMap.html
+///
+#[derive(Debug)]
+pub struct ShiftRegisterDriver<SPI, LP, D>
+where
+    SPI: spi::Write<u8>,
+    LP: OutputPin,
+    D: DelayUs<u16>,
+{
+    spi: SPI,
+    latch: LP,
+    pins: PinMap,
+    backlight: bool,
+    rs: RegisterSelect,
+    delay: D,
+}
+
+impl<SPI, LP, D> ShiftRegisterDriver<SPI, LP, D>
+where
+    SPI: spi::Write<u8>,
+    LP: OutputPin,
+    D: DelayUs<u16>,
+{
+    /// Create a new instance of driver using the Adafruit backpack wiring.
+    ///
+    /// Same as calling [with_pins()] with [PinMap::SPI_BACKPACK].
+    ///
+    /// ## Arguments
+    ///
+    /// * `spi` - An already setup SPI bus the shift register is connected to.
+    /// * `latch` - An already setup output GPIO pin that is connected to the
+    ///   storage register clock (`RCLK`) input of the shift register.
+    /// * `delay` - Used to give the display time to process commands.
+    ///
+    /// [with_pins()]: #method.with_pins
+    /// [PinMap::SPI_BACKPACK]: struct.PinMap.html#associatedconstant.SPI_BACKPACK
+    ///
+    pub fn new(spi: SPI, latch: LP, delay: D) -> ShiftRegisterDriver<SPI, LP, D> {
+        Self::from_pins(spi, latch, PinMap::SPI_BACKPACK, delay)
+    }
+    /// Create a new instance of driver for a shift register with custom wiring.
+    ///
+    /// ## Arguments
+    ///
+    /// * `spi` - An already setup SPI bus the shift register is connected to.
+    /// * `latch` - An already setup output GPIO pin that is connected to the
+    ///   storage register clock (`RCLK`) input of the shift register.
+    /// * `pins` - Which shift register output bits are connected to which
+    ///   display pins. All bit numbers must be in the range 0 to 7.
+    /// * `delay` - Used to give the display time to process commands.
+    ///
+    /// ## Errors
+    /// Returns [InvalidPinMap] if any of the bit numbers are out of range.
+    ///
+    /// [InvalidPinMap]: enum.HdError.html#variant.InvalidPinMap
+    ///
+    pub fn with_pins(spi: SPI, latch: LP, pins: PinMap, delay: D) -> Result<Self> {
+        pins.check(8)?;
+        Ok(Self::from_pins(spi, latch, pins, delay))
+    }
+    fn from_pins(spi: SPI, latch: LP, pins: PinMap, delay: D) -> Self {
+        ShiftRegisterDriver {
+            spi,
+            latch,
+            pins,
+            backlight: true,
+            rs: RegisterSelect::Data,
+            delay,
+        }
+    }
+    /// Returns `true` when the backlight is on.
+    pub fn backlight(&self) -> bool {
+        self.backlight
+    }
+    /// Turn the backlight on or off.
+    ///
+    /// The new state is latched onto the outputs right away and is kept for
+    /// all following commands and data writes.
+    pub fn set_backlight(&mut self, on: bool) -> Result {
+        self.backlight = on;
+        let frame = self.pins.backlight_bits(on);
+        self.send(&[frame])
+    }
+    fn send(&mut self, frames: &[u16]) -> Result {
+        for frame in frames {
+            self.latch.set_low().map_err(|_| SetOutputPin("latch"))?;
+            self.spi.write(&[*frame as u8]).map_err(|_| SpiWrite)?;
+            // Outputs change on the rising edge.
+            self.latch.set_high().map_err(|_| SetOutputPin("latch"))?;
+        }
+        Ok(())
+    }
+    fn write_byte(&mut self, byte: u8) -> Result {
+        let high = self.pins.frame(byte >> 4, self.rs, self.backlight);
+        let low = self.pins.frame(byte & 0b0000_1111, self.rs, self.backlight);
+        let enable = self.pins.enable();
+        // Each nibble is latched with enable low first, like the GPIO driver's
+        // enable toggle, so the display gets its setup time before the strobe.
+        self.send(&[high, high | enable, high, low, low | enable, low])
+    }
+}
+
+impl<SPI, LP, D> HD44780 for ShiftRegisterDriver<SPI, LP, D>
+where
+    SPI: spi::Write<u8>,
+    LP: OutputPin,
+    D: DelayUs<u16>,
+{
+    const COMMAND_DELAY: u16 = 41;
+    fn command(&mut self, byte: u8, delay: u16) -> Result {
+        // Switch to command mode.
+        self.rs = RegisterSelect::Cmnd;
+        // Send command.
+        let result = self.write_byte(byte);
+        // Switch back to data mode.
+        self.rs = RegisterSelect::Data;
+        result?;
+        // Given HD44780 time to process command before sending anything else.
+        self.delay.delay_us(delay);
+        Ok(())
+    }
+    fn bus_len(&self) -> usize {
+        4
+    }
+    fn prepare_init(&mut self) -> Result {
+        // Insure display has had time to stabilize if just powered on.
+        // This takes between 15 to 40ms depending on supplied voltage.
+        // 1000 times the command delay should be enough.
+        let delay = Self::COMMAND_DELAY * 1000;
+        self.delay.delay_us(delay);
+        // Start from a known output state with enable low.
+        let frame = self.pins.backlight_bits(self.backlight);
+        self.send(&[frame])
+    }
+}
+
+impl<SPI, LP, D> Write for ShiftRegisterDriver<SPI, LP, D>
+where
+    SPI: spi::Write<u8>,
+    LP: OutputPin,
+    D: DelayUs<u16>,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for byte in buf {
+            self.write_byte(*byte)?;
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
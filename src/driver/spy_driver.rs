@@ -21,46 +21,148 @@
 // SOFTWARE.
 //! Contains a test driver and associated structs that does __NOT__ connect to any hardware.
 
-use crate::{DisplayMode, EntryMode, FunctionMode, Result, HD44780};
+use crate::{Clock, Instruction, Result, VirtualClock, HD44780};
+use std::convert::TryFrom;
 use std::io::{Result as IOResult, Write};
-use std::time::Instant;
+use std::time::Duration;
 
-/// A very basic testing driver that records arguments given for commands and writes.
+/// A very basic testing driver that records everything sent to it in order.
+///
+/// ## Remarks
+///
+/// Commands, data writes, and [init()] calls all go into a single [Event] log
+/// so tests can check how they were interleaved, like a DD RAM address being
+/// set before the data written to it.
+///
+/// [init()] is the shared default so the whole reset sequence a driver with
+/// a 4 or 8 bit bus (see [with_bus_len()]) would send is recorded after an
+/// [Event::Init] marker.
+///
+/// Events are time stamped by a [Clock] which is also waited on for each
+/// command's delay. The default [VirtualClock] never sleeps so the time
+/// between events is exactly the delays asked for, while a different clock can
+/// be given with [with_clock()].
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let mut spy = SpyDriver::new();
+/// spy.set_dd_ram_addr(0x40)?;
+/// spy.write_all(b"Hi")?;
+/// assert_eq!(spy.events()[0].instruction(), Some(Instruction::SetDdRamAddr(0x40)));
+/// assert_eq!(spy.events()[1].data(), Some(&b"Hi"[..]));
+///
+/// let mut spy = SpyDriver::new();
+/// spy.clear_display()?;
+/// spy.return_home()?;
+/// assert_eq!(spy.events()[1].when() - spy.events()[0].when(), Duration::from_micros(1554));
+/// ```
+///
+/// [init()]: trait.HD44780.html#method.init
+/// [Event]: enum.Event.html
+/// [with_bus_len()]: #method.with_bus_len
+/// [Event::Init]: enum.Event.html#variant.Init
+/// [Clock]: trait.Clock.html
+/// [VirtualClock]: struct.VirtualClock.html
+/// [with_clock()]: #method.with_clock
+///
 #[derive(Debug)]
-pub struct SpyDriver {
-    /// List of commands sent to driver.
-    pub commands: Vec<Command>,
-    /// Init command is handled differently because of extra arguments.
-    ///
-    /// Since init isn't a single command to the hardware but a special sequence
-    /// of commands and mode settings that is used to reset the hardware it is
-    /// handled differently here as well.
-    pub init_command: Option<(
-        Instant,
-        Option<FunctionMode>,
-        Option<DisplayMode>,
-        Option<EntryMode>,
-    )>,
-    /// Any non-command data writes.
-    ///
-    /// Both writes to CG RAM and DD RAM end up here as the actual hardware
-    /// determines which is being written by proceeding command that was given.
-    pub writes: Vec<(Instant, Vec<u8>)>,
+pub struct SpyDriver<C = VirtualClock>
+where
+    C: Clock,
+{
+    events: Vec<Event>,
+    bus_len: usize,
+    clock: C,
 }
 
 impl SpyDriver {
+    /// Create a new driver with an empty event log acting like it has an 8
+    /// bit bus.
     pub fn new() -> Self {
+        Self::with_bus_len(8)
+    }
+    /// Create a new driver acting like it has a data bus with `bus_len` pins.
+    ///
+    /// ## Arguments
+    /// * `bus_len` - Used by [init()] to pick the reset sequence, anything but
+    ///   4 or 8 makes it fail.
+    ///
+    /// [init()]: trait.HD44780.html#method.init
+    ///
+    pub fn with_bus_len(bus_len: usize) -> Self {
+        SpyDriver::with_clock(VirtualClock::new(), bus_len)
+    }
+}
+
+impl<C> SpyDriver<C>
+where
+    C: Clock,
+{
+    /// Create a new driver using the given clock for time stamps and delays.
+    ///
+    /// ## Arguments
+    /// * `clock` - Time source that is also waited on after each command.
+    /// * `bus_len` - Same as in [with_bus_len()].
+    ///
+    /// [with_bus_len()]: #method.with_bus_len
+    ///
+    pub fn with_clock(clock: C, bus_len: usize) -> Self {
         SpyDriver {
-            commands: vec![],
-            init_command: None,
-            writes: vec![],
+            events: vec![],
+            bus_len,
+            clock,
         }
     }
+    /// The clock used for time stamps and delays.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+    /// Everything sent to the driver from oldest to newest.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+    /// Just the commands sent to the driver.
+    pub fn commands(&self) -> impl Iterator<Item = &Command> {
+        self.events.iter().filter_map(|e| match e {
+            Event::Command(command) => Some(command),
+            _ => None,
+        })
+    }
+    /// Decoded instructions of all the commands sent to the driver.
+    pub fn instructions(&self) -> Vec<Instruction> {
+        self.commands().filter_map(Command::instruction).collect()
+    }
+    /// All data bytes written to the driver joined together.
+    ///
+    /// Both writes to CG RAM and DD RAM end up here as the actual hardware
+    /// determines which is being written by proceeding command that was given.
+    pub fn written(&self) -> Vec<u8> {
+        self.events
+            .iter()
+            .filter_map(Event::data)
+            .flatten()
+            .copied()
+            .collect()
+    }
+    /// Empty the event log.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+impl Default for SpyDriver {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl Write for SpyDriver {
+impl<C> Write for SpyDriver<C>
+where
+    C: Clock,
+{
     fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
-        self.writes.push((Instant::now(), Vec::from(buf)));
+        self.events
+            .push(Event::Data(self.clock.now(), Vec::from(buf)));
         Ok(buf.len())
     }
     fn flush(&mut self) -> IOResult<()> {
@@ -68,39 +170,108 @@ impl Write for SpyDriver {
     }
 }
 
-impl HD44780 for SpyDriver {
-    const COMMAND_DELAY: u16 = 0;
+impl<C> HD44780 for SpyDriver<C>
+where
+    C: Clock,
+{
+    /// Minimum from the HD44780 datasheet so delays look like a real driver's.
+    const COMMAND_DELAY: u16 = 37;
     fn command(&mut self, byte: u8, delay: u16) -> Result {
-        self.commands.push(Command {
-            when: Instant::now(),
+        self.events.push(Event::Command(Command {
+            when: self.clock.now(),
             byte,
             delay,
-        });
+        }));
+        self.clock.wait_us(delay as u32);
         Ok(())
     }
-    fn init<FSM, DCM, EMSM>(&mut self, fs_mode: FSM, dc_mode: DCM, ems_mode: EMSM) -> Result
-    where
-        FSM: Into<Option<FunctionMode>>,
-        DCM: Into<Option<DisplayMode>>,
-        EMSM: Into<Option<EntryMode>>,
-    {
-        self.init_command = Some((
-            Instant::now(),
-            fs_mode.into(),
-            dc_mode.into(),
-            ems_mode.into(),
-        ));
+    fn bus_len(&self) -> usize {
+        self.bus_len
+    }
+    fn prepare_init(&mut self) -> Result {
+        self.events.push(Event::Init(self.clock.now()));
         Ok(())
     }
 }
 
+/// Something recorded by [SpyDriver].
+///
+/// [SpyDriver]: struct.SpyDriver.html
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// A command byte.
+    Command(Command),
+    /// Bytes written to CG or DD RAM.
+    Data(Duration, Vec<u8>),
+    /// Start of the commands sent by [init()].
+    ///
+    /// [init()]: trait.HD44780.html#method.init
+    ///
+    Init(Duration),
+}
+
+impl Event {
+    /// When the event happened as time since the driver's clock started.
+    pub fn when(&self) -> Duration {
+        match self {
+            Event::Command(command) => command.when,
+            Event::Data(when, _) | Event::Init(when) => *when,
+        }
+    }
+    /// Expected delay(µs) after the event, always 0 except for commands.
+    pub fn delay(&self) -> u16 {
+        match self {
+            Event::Command(command) => command.delay,
+            _ => 0,
+        }
+    }
+    /// The command if this is a command event.
+    pub fn command(&self) -> Option<&Command> {
+        match self {
+            Event::Command(command) => Some(command),
+            _ => None,
+        }
+    }
+    /// Decoded instruction if this is a command event.
+    pub fn instruction(&self) -> Option<Instruction> {
+        self.command().and_then(Command::instruction)
+    }
+    /// Bytes written if this is a data event.
+    pub fn data(&self) -> Option<&[u8]> {
+        match self {
+            Event::Data(_, bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+}
+
 /// Structure in [SpyDriver] to record commands.
 ///
 /// [SpyDriver]: struct.SpyDriver.html
 ///
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Command {
-    when: Instant,
+    when: Duration,
     byte: u8,
     delay: u16,
 }
+
+impl Command {
+    /// When the command was sent as time since the driver's clock started.
+    pub fn when(&self) -> Duration {
+        self.when
+    }
+    /// The command byte as sent to the driver.
+    pub fn byte(&self) -> u8 {
+        self.byte
+    }
+    /// Expected delay(µs) after the command.
+    pub fn delay(&self) -> u16 {
+        self.delay
+    }
+    /// The decoded command, `None` for `0x00` which is not a command.
+    pub fn instruction(&self) -> Option<Instruction> {
+        Instruction::try_from(self.byte).ok()
+    }
+}
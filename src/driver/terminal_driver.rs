@@ -0,0 +1,267 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Contains a driver that draws the display in an ANSI terminal instead of
+//! using any hardware.
+
+use crate::{DisplayMode, EmulatorDriver, EntryMode, FunctionMode, Result, HD44780};
+use std::fmt;
+use std::io::{Result as IOResult, Write};
+
+/// Keys that can be decoded from terminal input by [TerminalDriver].
+///
+/// [TerminalDriver]: struct.TerminalDriver.html
+///
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Escape,
+    Backspace,
+    /// Any other printable ASCII key.
+    Char(char),
+}
+
+/// A driver that shows what a HD44780 display would in an ANSI terminal.
+///
+/// Uses an [EmulatorDriver] to keep track of the display state and redraws the
+/// visible window inside of a border after every command or data write.
+///
+/// ## Remarks
+///
+/// The cursor is shown underlined and the blinking block cursor as blinking
+/// reverse video.
+/// Custom characters are shown as a shaded block based on how many of their
+/// pixels are on and any other characters outside of `0x20`-`0x7D` as `?`.
+///
+/// Keyboard input is __NOT__ read directly so it does not block, instead the
+/// application passes anything read from the terminal to [input()] which calls
+/// the handler set with [on_key()] for each decoded key.
+/// The terminal should be in raw mode (`stty raw -echo`) so keys are sent
+/// without waiting for enter.
+///
+/// [EmulatorDriver]: struct.EmulatorDriver.html
+/// [input()]: #method.input
+/// [on_key()]: #method.on_key
+///
+pub struct TerminalDriver<W>
+where
+    W: Write,
+{
+    lcd: EmulatorDriver,
+    out: W,
+    key_handler: Option<Box<dyn FnMut(Key)>>,
+}
+
+impl<W> TerminalDriver<W>
+where
+    W: Write,
+{
+    /// Create a new instance of driver.
+    ///
+    /// ## Arguments
+    /// * `out` - Where to draw the display, normally `stdout()`.
+    /// * `columns` - Number of characters per row on the emulated display.
+    /// * `rows` - Number of rows on the emulated display (1 to 4).
+    ///
+    /// ## Examples
+    /// ```edition2018,ignore
+    /// let mut lcd = TerminalDriver::new(stdout(), 16, 2);
+    /// lcd.on_key(|key| println!("{:?}", key));
+    /// lcd.init(FunctionMode::LINES_2, DisplayMode::DISPLAY_ON, None)?;
+    /// ```
+    pub fn new(out: W, columns: u8, rows: u8) -> Self {
+        TerminalDriver {
+            lcd: EmulatorDriver::new(columns, rows),
+            out,
+            key_handler: None,
+        }
+    }
+    /// Get a reference to the emulated display state.
+    pub fn emulator(&self) -> &EmulatorDriver {
+        &self.lcd
+    }
+    /// Get a reference to where the display is drawn.
+    pub fn get_ref(&self) -> &W {
+        &self.out
+    }
+    /// Set the handler called for each key decoded by [input()].
+    ///
+    /// [input()]: #method.input
+    ///
+    pub fn on_key<F>(&mut self, handler: F)
+    where
+        F: FnMut(Key) + 'static,
+    {
+        self.key_handler = Some(Box::new(handler));
+    }
+    /// Decode terminal input and pass each key to the key handler.
+    ///
+    /// Understands the ANSI arrow key sequences from both normal and
+    /// application cursor key modes.
+    /// Unknown escape sequences and control characters are ignored.
+    ///
+    /// ## Arguments
+    /// * `bytes` - Bytes read from the terminal.
+    pub fn input(&mut self, bytes: &[u8]) {
+        let keys = decode_keys(bytes);
+        if let Some(handler) = self.key_handler.as_mut() {
+            keys.into_iter().for_each(handler);
+        }
+    }
+    /// Draw the visible window of the display.
+    pub fn redraw(&mut self) -> Result {
+        let mode = self.lcd.display_mode();
+        let cursor = self.lcd.cursor();
+        let border = format!("+{}+\r\n", "-".repeat(self.lcd.columns() as usize));
+        let mut frame = String::from("\x1b[H");
+        frame.push_str(&border);
+        for (row, glyphs) in self.lcd.glyphs().iter().enumerate() {
+            frame.push('|');
+            for (col, &code) in glyphs.iter().enumerate() {
+                let at_cursor = mode.contains(DisplayMode::DISPLAY_ON)
+                    && cursor == Some((col as u8, row as u8));
+                let mut style = String::new();
+                if at_cursor && mode.contains(DisplayMode::CURSOR_ON) {
+                    style.push_str("\x1b[4m");
+                }
+                if at_cursor && mode.contains(DisplayMode::BLINK_ON) {
+                    style.push_str("\x1b[5;7m");
+                }
+                frame.push_str(&style);
+                frame.push(self.glyph_char(code));
+                if !style.is_empty() {
+                    frame.push_str("\x1b[0m");
+                }
+            }
+            frame.push_str("|\r\n");
+        }
+        frame.push_str(&border);
+        self.out.write_all(frame.as_bytes())?;
+        self.out.flush()?;
+        Ok(())
+    }
+    fn glyph_char(&self, code: u8) -> char {
+        match code {
+            0x00..=0x0F => {
+                let pixels: u32 = self
+                    .lcd
+                    .custom_char(code)
+                    .iter()
+                    .map(|row| (row & 0x1F).count_ones())
+                    .sum();
+                // 40 pixels in a 5x8 character.
+                match pixels {
+                    0 => ' ',
+                    1..=10 => '░',
+                    11..=20 => '▒',
+                    21..=30 => '▓',
+                    _ => '█',
+                }
+            }
+            0x20..=0x7D => code as char,
+            _ => '?',
+        }
+    }
+}
+
+impl<W> fmt::Debug for TerminalDriver<W>
+where
+    W: Write + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The key handler is a closure so only if there is one can be shown.
+        f.debug_struct("TerminalDriver")
+            .field("lcd", &self.lcd)
+            .field("out", &self.out)
+            .field("key_handler", &self.key_handler.is_some())
+            .finish()
+    }
+}
+
+impl<W> HD44780 for TerminalDriver<W>
+where
+    W: Write,
+{
+    const COMMAND_DELAY: u16 = 0;
+    fn command(&mut self, byte: u8, delay: u16) -> Result {
+        self.lcd.command(byte, delay)?;
+        self.redraw()
+    }
+    fn init<FM, DM, EM>(&mut self, fs_mode: FM, dc_mode: DM, ems_mode: EM) -> Result
+    where
+        FM: Into<Option<FunctionMode>>,
+        DM: Into<Option<DisplayMode>>,
+        EM: Into<Option<EntryMode>>,
+    {
+        self.lcd.init(fs_mode, dc_mode, ems_mode)?;
+        // Clear the terminal so nothing is left around the display.
+        self.out.write_all(b"\x1b[2J")?;
+        self.redraw()
+    }
+}
+
+impl<W> Write for TerminalDriver<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
+        let len = self.lcd.write(buf)?;
+        self.redraw()?;
+        Ok(len)
+    }
+    fn flush(&mut self) -> IOResult<()> {
+        self.out.flush()
+    }
+}
+
+fn decode_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            0x1B => {
+                // Both `ESC [` and `ESC O` are used for arrow keys.
+                if i + 2 < bytes.len() && (bytes[i + 1] == b'[' || bytes[i + 1] == b'O') {
+                    match bytes[i + 2] {
+                        b'A' => keys.push(Key::Up),
+                        b'B' => keys.push(Key::Down),
+                        b'C' => keys.push(Key::Right),
+                        b'D' => keys.push(Key::Left),
+                        _ => {}
+                    }
+                    i += 3;
+                    continue;
+                }
+                keys.push(Key::Escape);
+            }
+            b'\r' | b'\n' => keys.push(Key::Enter),
+            0x08 | 0x7F => keys.push(Key::Backspace),
+            c @ 0x20..=0x7E => keys.push(Key::Char(c as char)),
+            _ => {}
+        }
+        i += 1;
+    }
+    keys
+}
@@ -26,18 +26,65 @@ use thiserror::Error;
 /// Provides a shared set of error types.
 #[derive(Error, Debug)]
 pub enum HdError {
+    /// Used if the display stays busy longer than the given timeout(µs).
+    #[error("Display was still busy after {0}µs")]
+    BusyTimeout(u32),
+    /// Used if more custom characters are needed at once than CG RAM can hold.
+    #[error("All {0} CG RAM slots are used by the current frame or the display")]
+    CgRamFull(u8),
+    /// Used if a GPIO character device line handle can not be used.
+    #[error("Could not {0} GPIO lines")]
+    GpioLines(&'static str),
+    /// Used if an I²C bus read from a port expander fails.
+    #[error("Could not read from I²C device at address {0:#04x}")]
+    I2cRead(u8),
+    /// Used if an I²C bus write to a port expander fails.
+    #[error("Could not write to I²C device at address {0:#04x}")]
+    I2cWrite(u8),
+    /// Used if a CG RAM slot is not available in the current font mode.
+    #[error("CG RAM slot {0} can not be used with the current font mode")]
+    InvalidCgRamSlot(u8),
+    /// Used if a custom character can not be built or used as given.
+    #[error("Invalid custom character: {0}")]
+    InvalidCustomChar(&'static str),
     /// Used if data bus given is not 4 or 8 bits long.
     #[error("Data bus must be 4 or 8 bits")]
     InvalidDataBusLen,
+    /// Used if a DD RAM address with bit 7 set, which picks the second
+    /// controller of a 40x4 display, is given to a driver with only one.
+    #[error("DD RAM address {0:#04x} needs a second controller")]
+    InvalidDdRamAddr(u8),
     /// Used by [function_set()] for un-supported mode setting.
     #[error("Can NOT use 5x10 font with 2 line mode")]
     InvalidLineAndFontMode,
+    /// Used if a pin map uses an output bit the expander or shift register
+    /// does not have.
+    #[error("Pin map uses output bit {0} which does not exist")]
+    InvalidPinMap(u8),
+    /// Used if a column and row position is not on the display.
+    #[error("Position column {0}, row {1} is off-screen")]
+    OffScreen(u8, u8),
+    /// Used if given input GPIO pin can not be read.
+    #[error("Could not read {0} input pin")]
+    ReadInputPin(&'static str),
     /// Used if given output GPIO pin can not be set.
     #[error("Could not set {0} output pin")]
     SetOutputPin(&'static str),
+    /// Used if the direction of a GPIO pin can not be changed.
+    #[error("Could not change direction of {0} pin")]
+    SetPinDirection(&'static str),
+    /// Used if a SPI bus write to a shift register fails.
+    #[error("Could not write to SPI bus")]
+    SpiWrite,
     /// Used when decoding HD44780 commands.
     #[error("Could not find command, was given {0}")]
     UnknownCommand(u8),
+    /// Used when a character is not in the display's character ROM.
+    #[error("Character {0:?} is not in the character ROM")]
+    UnmappableChar(char),
+    /// Used when a driver or its hardware can not do what was asked.
+    #[error("{0} is not supported by this driver")]
+    Unsupported(&'static str),
     /// Used if under-laying IO Error happens.
     #[error("IO write failed")]
     Write(#[from] std::io::Error),
@@ -45,9 +92,12 @@ pub enum HdError {
 
 impl From<HdError> for std::io::Error {
     fn from(he: HdError) -> Self {
-        he.into()
+        match he {
+            HdError::Write(e) => e,
+            he => std::io::Error::other(he),
+        }
     }
 }
 
 /// Common result used as return type from functions in library.
-pub type Result = std::result::Result<(), HdError>;
+pub type Result<T = ()> = std::result::Result<T, HdError>;
@@ -0,0 +1,144 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Describes how the rows and columns of a display map to DD RAM addresses.
+
+use crate::error::HdError::OffScreen;
+use crate::{FunctionMode, Result};
+
+/// Layout of a display and how its positions map to DD RAM addresses.
+///
+/// ## Remarks
+///
+/// Most displays use `0x00` and `0x40` for the first 2 rows and displays with
+/// 4 rows continue those 2 lines for the last 2 rows so they start at
+/// `columns` and `0x40 + columns`.
+/// The 16x1 "type 1" modules are wired as an 8x2 display so the right half of
+/// the row starts at `0x40` which is handled by `split_column`.
+///
+/// 40x4 displays have a second controller for the last 2 rows.
+/// [LCD_40X4] puts those rows at `0x80` and `0xC0` which is bit 7 added to the
+/// usual `0x00` and `0x40`. Bit 7 is not part of a real DD RAM address so
+/// drivers like [DualGpioDriver] use it to pick the controller while drivers
+/// with a single controller return an error for those rows.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let geometry = Geometry::LCD_20X4;
+/// lcd.init(geometry.function_mode(), DisplayMode::DISPLAY_ON, None)?;
+/// lcd.set_cursor(&geometry, 0, 3)?;
+/// ```
+///
+/// [LCD_40X4]: #associatedconstant.LCD_40X4
+/// [DualGpioDriver]: struct.DualGpioDriver.html
+///
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Geometry {
+    /// Number of characters per row.
+    pub columns: u8,
+    /// Number of rows (1 to 4).
+    pub rows: u8,
+    /// DD RAM address of the first column of each row.
+    pub row_offsets: [u8; 4],
+    /// First column which continues at `0x40` past the row offset if any.
+    pub split_column: Option<u8>,
+}
+
+impl Geometry {
+    /// 8 characters by 1 row.
+    pub const LCD_8X1: Geometry = Geometry::new(8, 1);
+    /// 16 characters by 1 row addressed as a single line (type 2).
+    pub const LCD_16X1: Geometry = Geometry::new(16, 1);
+    /// 16 characters by 1 row addressed as 2 lines of 8 (type 1).
+    pub const LCD_16X1_TYPE1: Geometry = Geometry {
+        columns: 16,
+        rows: 1,
+        row_offsets: [0x00, 0x40, 0x00, 0x40],
+        split_column: Some(8),
+    };
+    /// 16 characters by 2 rows.
+    pub const LCD_16X2: Geometry = Geometry::new(16, 2);
+    /// 16 characters by 4 rows.
+    pub const LCD_16X4: Geometry = Geometry::new(16, 4);
+    /// 20 characters by 2 rows.
+    pub const LCD_20X2: Geometry = Geometry::new(20, 2);
+    /// 20 characters by 4 rows.
+    pub const LCD_20X4: Geometry = Geometry::new(20, 4);
+    /// 40 characters by 2 rows.
+    pub const LCD_40X2: Geometry = Geometry::new(40, 2);
+    /// 40 characters by 4 rows using two controllers.
+    pub const LCD_40X4: Geometry = Geometry {
+        columns: 40,
+        rows: 4,
+        row_offsets: [0x00, 0x40, 0x80, 0xC0],
+        split_column: None,
+    };
+    /// Create a new geometry using the common row offsets.
+    ///
+    /// ## Arguments
+    /// * `columns` - Number of characters per row.
+    /// * `rows` - Number of rows (1 to 4).
+    pub const fn new(columns: u8, rows: u8) -> Self {
+        Geometry {
+            columns,
+            rows,
+            row_offsets: [0x00, 0x40, columns, columns.wrapping_add(0x40)],
+            split_column: None,
+        }
+    }
+    /// DD RAM address of a position.
+    ///
+    /// ## Arguments
+    /// * `col` - Column starting from 0.
+    /// * `row` - Row starting from 0.
+    ///
+    /// ## Errors
+    /// Returns [OffScreen] if the position is outside of the display.
+    ///
+    /// [OffScreen]: enum.HdError.html#variant.OffScreen
+    ///
+    pub fn address(&self, col: u8, row: u8) -> Result<u8> {
+        if col >= self.columns || row >= self.rows.min(4) {
+            return Err(OffScreen(col, row));
+        }
+        let offset = self.row_offsets[row as usize];
+        let address = match self.split_column {
+            Some(split) if col >= split => offset.wrapping_add(col - split).wrapping_add(0x40),
+            _ => offset.wrapping_add(col),
+        };
+        // Bit 7 of the row offset selects the controller and is kept as is.
+        Ok((offset & 0b1000_0000) | (address & 0b0111_1111))
+    }
+    /// Position of a DD RAM address if it is on the display.
+    pub fn position(&self, address: u8) -> Option<(u8, u8)> {
+        (0..self.rows.min(4))
+            .flat_map(|row| (0..self.columns).map(move |col| (col, row)))
+            .find(|&(col, row)| self.address(col, row).ok() == Some(address))
+    }
+    /// Line mode the display needs to be initialized with.
+    pub fn function_mode(&self) -> FunctionMode {
+        if self.rows > 1 || self.split_column.is_some() {
+            FunctionMode::LINES_2
+        } else {
+            FunctionMode::LINES_1
+        }
+    }
+}
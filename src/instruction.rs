@@ -0,0 +1,174 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Typed HD44780 instructions and their conversion to and from command bytes.
+
+use crate::error::HdError::{self, UnknownCommand};
+use crate::{DisplayMode, EntryMode, FunctionMode, ShiftMode};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A single HD44780 instruction with its parameters.
+///
+/// ## Remarks
+///
+/// Every command byte is decided by its highest set bit so any byte except
+/// `0x00` decodes to an instruction.
+/// Bits below the parameters are ignored by the controller so decoding and
+/// then encoding a byte like `0x03` gives back `0x02` for [ReturnHome].
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let instruction = Instruction::try_from(0x0C)?;
+/// assert_eq!(instruction, Instruction::DisplayControl(DisplayMode::DISPLAY_ON));
+/// assert_eq!(instruction.encode(), 0x0C);
+/// println!("{}", instruction);
+/// ```
+///
+/// [ReturnHome]: #variant.ReturnHome
+///
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Instruction {
+    /// Clears the display and sets DD RAM address 0.
+    ClearDisplay,
+    /// Sets DD RAM address 0 and undoes any display shift.
+    ReturnHome,
+    /// Sets cursor move direction and if the display shifts on writes.
+    EntryModeSet(EntryMode),
+    /// Sets the display, cursor, and blink on or off.
+    DisplayControl(DisplayMode),
+    /// Moves the cursor or shifts the display one position.
+    CursorShift(ShiftMode),
+    /// Sets the data bus length, number of lines, and font.
+    FunctionSet(FunctionMode),
+    /// Sets the CG RAM address, only the lower 6 bits are used.
+    SetCgRamAddr(u8),
+    /// Sets the DD RAM address, only the lower 7 bits are used.
+    SetDdRamAddr(u8),
+}
+
+impl Instruction {
+    /// The command byte sent to the controller.
+    pub const fn encode(self) -> u8 {
+        match self {
+            Instruction::ClearDisplay => 0x01,
+            Instruction::ReturnHome => 0x02,
+            Instruction::EntryModeSet(mode) => 0x04 | mode.bits(),
+            Instruction::DisplayControl(mode) => 0x08 | mode.bits(),
+            Instruction::CursorShift(mode) => 0x10 | mode.bits(),
+            Instruction::FunctionSet(mode) => 0x20 | mode.bits(),
+            Instruction::SetCgRamAddr(address) => 0x40 | (address & 0b0011_1111),
+            Instruction::SetDdRamAddr(address) => 0x80 | (address & 0b0111_1111),
+        }
+    }
+    /// Time(µs) the controller needs to finish the instruction.
+    ///
+    /// ## Arguments
+    /// * `command_delay` - A driver's [COMMAND_DELAY] which most instructions
+    ///   use as is.
+    ///
+    /// [COMMAND_DELAY]: trait.HD44780.html#associatedconstant.COMMAND_DELAY
+    ///
+    pub const fn delay(self, command_delay: u16) -> u16 {
+        match self {
+            // Per HD44780 datasheet clear and home take 1.52ms.
+            // 37µs * 42 = 1.554ms for a little fudge factor.
+            Instruction::ClearDisplay | Instruction::ReturnHome => command_delay * 42,
+            _ => command_delay,
+        }
+    }
+}
+
+impl From<Instruction> for u8 {
+    fn from(instruction: Instruction) -> Self {
+        instruction.encode()
+    }
+}
+
+impl TryFrom<u8> for Instruction {
+    type Error = HdError;
+    /// Decode a command byte.
+    ///
+    /// ## Errors
+    /// Returns [UnknownCommand] for `0x00` which is not an instruction.
+    ///
+    /// [UnknownCommand]: enum.HdError.html#variant.UnknownCommand
+    ///
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        Ok(match byte {
+            0x80..=0xFF => Instruction::SetDdRamAddr(byte & 0b0111_1111),
+            0x40..=0x7F => Instruction::SetCgRamAddr(byte & 0b0011_1111),
+            0x20..=0x3F => Instruction::FunctionSet(FunctionMode::from_bits_truncate(byte)),
+            0x10..=0x1F => Instruction::CursorShift(ShiftMode::from_bits_truncate(byte)),
+            0x08..=0x0F => Instruction::DisplayControl(DisplayMode::from_bits_truncate(byte)),
+            0x04..=0x07 => Instruction::EntryModeSet(EntryMode::from_bits_truncate(byte)),
+            0x02..=0x03 => Instruction::ReturnHome,
+            0x01 => Instruction::ClearDisplay,
+            0x00 => return Err(UnknownCommand(byte)),
+        })
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pick = |on: bool, set: &'static str, clear: &'static str| if on { set } else { clear };
+        match *self {
+            Instruction::ClearDisplay => write!(f, "Clear display"),
+            Instruction::ReturnHome => write!(f, "Return home"),
+            Instruction::EntryModeSet(mode) => write!(
+                f,
+                "Entry mode set: {}, shift {}",
+                pick(
+                    mode.contains(EntryMode::ENTRY_LEFT),
+                    "increment",
+                    "decrement"
+                ),
+                pick(
+                    mode.contains(EntryMode::ENTRY_SHIFT_DISPLAY),
+                    "display",
+                    "cursor"
+                ),
+            ),
+            Instruction::DisplayControl(mode) => write!(
+                f,
+                "Display control: display {}, cursor {}, blink {}",
+                pick(mode.contains(DisplayMode::DISPLAY_ON), "on", "off"),
+                pick(mode.contains(DisplayMode::CURSOR_ON), "on", "off"),
+                pick(mode.contains(DisplayMode::BLINK_ON), "on", "off"),
+            ),
+            Instruction::CursorShift(mode) => write!(
+                f,
+                "Cursor shift: move {} {}",
+                pick(mode.contains(ShiftMode::DISPLAY_MOVE), "display", "cursor"),
+                pick(mode.contains(ShiftMode::MOVE_RIGHT), "right", "left"),
+            ),
+            Instruction::FunctionSet(mode) => write!(
+                f,
+                "Function set: {} bit, {} line, 5x{} dots",
+                pick(mode.contains(FunctionMode::BITS_8), "8", "4"),
+                pick(mode.contains(FunctionMode::LINES_2), "2", "1"),
+                pick(mode.contains(FunctionMode::DOTS_5X10), "10", "8"),
+            ),
+            Instruction::SetCgRamAddr(address) => write!(f, "Set CG RAM address {:#04x}", address),
+            Instruction::SetDdRamAddr(address) => write!(f, "Set DD RAM address {:#04x}", address),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Contains a wrapper which keeps track of what has been sent to a driver.
+
+use crate::error::HdError::{InvalidCgRamSlot, InvalidCustomChar};
+use crate::{
+    CustomChar, DisplayMode, EmulatorDriver, EntryMode, FunctionMode, Geometry, Result, HD44780,
+};
+use std::io::{Result as IOResult, Write};
+
+/// Wraps a driver and keeps track of the display state as commands and data
+/// are sent through it.
+///
+/// Most drivers can not read anything back from the display so this keeps an
+/// [EmulatorDriver] in sync with the real display which makes it possible to
+/// do things like [load_custom_char()] without losing the cursor position.
+///
+/// ## Remarks
+///
+/// Anything sent directly to the wrapped driver will not be tracked so the
+/// driver can only be gotten back with [into_inner()].
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let mut lcd = Lcd::new(driver, Geometry::LCD_20X4);
+/// lcd.init(FunctionMode::LINES_2, DisplayMode::DISPLAY_ON, None)?;
+/// lcd.set_cursor(5, 2)?;
+/// write!(lcd, "Hello")?;
+/// ```
+///
+/// [EmulatorDriver]: struct.EmulatorDriver.html
+/// [load_custom_char()]: #method.load_custom_char
+/// [into_inner()]: #method.into_inner
+///
+#[derive(Debug)]
+pub struct Lcd<D>
+where
+    D: HD44780,
+{
+    driver: D,
+    shadow: EmulatorDriver,
+}
+
+impl<D> Lcd<D>
+where
+    D: HD44780,
+{
+    /// Create a new wrapper.
+    ///
+    /// The display state starts as it would be after the controller's power on
+    /// reset so [init()] should be used before anything else.
+    ///
+    /// ## Arguments
+    /// * `driver` - The driver being wrapped.
+    /// * `geometry` - Layout of the display.
+    ///
+    /// [init()]: trait.HD44780.html#method.init
+    ///
+    pub fn new(driver: D, geometry: Geometry) -> Self {
+        Lcd {
+            driver,
+            shadow: EmulatorDriver::with_geometry(geometry),
+        }
+    }
+    /// Get a reference to the wrapped driver.
+    pub fn get_ref(&self) -> &D {
+        &self.driver
+    }
+    /// Unwrap and return the driver.
+    pub fn into_inner(self) -> D {
+        self.driver
+    }
+    /// Layout of the display.
+    pub fn geometry(&self) -> &Geometry {
+        self.shadow.geometry()
+    }
+    /// The tracked display state.
+    pub fn state(&self) -> &EmulatorDriver {
+        &self.shadow
+    }
+    /// Move the cursor to a column and row of the display.
+    ///
+    /// Same as [HD44780::set_cursor()] using the wrapper's geometry.
+    ///
+    /// ## Arguments
+    /// * `col` - Column starting from 0.
+    /// * `row` - Row starting from 0.
+    ///
+    /// [HD44780::set_cursor()]: trait.HD44780.html#method.set_cursor
+    ///
+    pub fn set_cursor(&mut self, col: u8, row: u8) -> Result {
+        let geometry = *self.geometry();
+        HD44780::set_cursor(self, &geometry, col, row)
+    }
+    /// Load a custom character into CG RAM.
+    ///
+    /// The DD or CG RAM address and entry mode are restored afterwards so text
+    /// output continues where it was.
+    ///
+    /// ## Arguments
+    /// * `slot` - Which custom character to replace, 0 to 7 with the 5x8 font
+    ///   or 0 to 3 with the 5x10 font.
+    /// * `char` - The bitmap to load. With the 5x10 font a 5x8 character has
+    ///   blank rows added below it.
+    ///
+    /// ## Errors
+    /// Returns [InvalidCgRamSlot] if the slot is not available with the current
+    /// font or [InvalidCustomChar] for a 5x10 character with the 5x8 font.
+    ///
+    /// [InvalidCgRamSlot]: enum.HdError.html#variant.InvalidCgRamSlot
+    /// [InvalidCustomChar]: enum.HdError.html#variant.InvalidCustomChar
+    ///
+    pub fn load_custom_char(&mut self, slot: u8, char: &CustomChar) -> Result {
+        let tall = self.shadow.is_5x10();
+        let (slots, size) = if tall { (4, 16) } else { (8, 8) };
+        if slot >= slots {
+            return Err(InvalidCgRamSlot(slot));
+        }
+        if char.is_5x10() && !tall {
+            return Err(InvalidCustomChar("5x10 character needs the 5x10 font"));
+        }
+        let address = self.shadow.address_counter();
+        let cg_ram = self.shadow.is_cg_ram();
+        let entry = self.shadow.entry_mode();
+        if !entry.contains(EntryMode::ENTRY_LEFT) {
+            self.entry_mode_set(entry | EntryMode::ENTRY_LEFT)?;
+        }
+        self.set_cg_ram_addr(slot * size)?;
+        let mut rows = char.rows().to_vec();
+        if tall {
+            // Clear what a 5x10 character left below a 5x8 one.
+            rows.resize(11, 0);
+        }
+        self.write_all(&rows)?;
+        if !entry.contains(EntryMode::ENTRY_LEFT) {
+            self.entry_mode_set(entry)?;
+        }
+        if cg_ram {
+            self.set_cg_ram_addr(address)
+        } else {
+            self.set_dd_ram_addr(address)
+        }
+    }
+}
+
+impl<D> HD44780 for Lcd<D>
+where
+    D: HD44780,
+{
+    const COMMAND_DELAY: u16 = D::COMMAND_DELAY;
+    fn command(&mut self, byte: u8, delay: u16) -> Result {
+        self.driver.command(byte, delay)?;
+        self.shadow.command(byte, delay)
+    }
+    fn init<FM, DM, EM>(&mut self, fs_mode: FM, dc_mode: DM, ems_mode: EM) -> Result
+    where
+        FM: Into<Option<FunctionMode>>,
+        DM: Into<Option<DisplayMode>>,
+        EM: Into<Option<EntryMode>>,
+    {
+        let fs = fs_mode.into();
+        let dc = dc_mode.into();
+        let ems = ems_mode.into();
+        self.driver.init(fs, dc, ems)?;
+        self.shadow.init(fs, dc, ems)
+    }
+    fn set_dd_ram_addr(&mut self, address: u8) -> Result {
+        // Passed on as is so drivers can see bit 7 which isn't in the command.
+        self.driver.set_dd_ram_addr(address)?;
+        self.shadow.set_dd_ram_addr(address)
+    }
+}
+
+impl<D> Write for Lcd<D>
+where
+    D: HD44780,
+{
+    fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
+        let len = self.driver.write(buf)?;
+        self.shadow.write_all(&buf[..len])?;
+        Ok(len)
+    }
+    fn flush(&mut self) -> IOResult<()> {
+        self.driver.flush()
+    }
+}
@@ -26,12 +26,50 @@
 #[macro_use]
 extern crate bitflags;
 
+mod bar_graph;
+mod big_font;
+mod cg_ram_manager;
+mod charset;
+mod clock;
 mod cmd;
+mod custom_char;
 mod driver;
 mod error;
+mod geometry;
+mod instruction;
+mod lcd;
+mod marquee;
+mod menu;
+mod render;
+mod sparkline;
 
-pub use crate::cmd::HD44780;
+pub use crate::bar_graph::BarGraph;
+pub use crate::big_font::{BigFont, BigFontHeight};
+pub use crate::cg_ram_manager::CgRamManager;
+pub use crate::charset::{Charset, Fallback};
+pub use crate::clock::{Clock, SystemClock, VirtualClock};
+pub use crate::cmd::{ReadableHD44780, HD44780};
 pub use crate::cmd::{DisplayMode, EntryMode, FunctionMode, ShiftMode};
-pub use crate::driver::gpio_driver::GpioDriver;
-pub use crate::driver::spy_driver::{Command, SpyDriver};
+pub use crate::custom_char::CustomChar;
+pub use crate::driver::cdev_driver::{CdevDriver, LineHandle};
+pub use crate::driver::char_lcd_driver::{CharLcdDriver, CHARLCD_BUFFER_WIDTH};
+pub use crate::driver::dual_gpio_driver::{Controller, DualGpioDriver};
+pub use crate::driver::emulator_driver::EmulatorDriver;
+pub use crate::driver::gpio_driver::{BidirectionalPin, GpioDriver, NoPin};
+pub use crate::driver::i2c_mcp230xx_driver::{
+    Buttons, I2cMcp230xxDriver, Mcp230xx, MCP230XX_ADDRESS,
+};
+pub use crate::driver::i2c_pcf8574_driver::{I2cPcf8574Driver, PCF8574A_ADDRESS, PCF8574_ADDRESS};
+pub use crate::driver::pin_map::PinMap;
+pub use crate::driver::serial_driver::{SerialDriver, SerialProtocol};
+pub use crate::driver::shift_register_driver::ShiftRegisterDriver;
+pub use crate::driver::spy_driver::{Command, Event, SpyDriver};
+pub use crate::driver::terminal_driver::{Key, TerminalDriver};
 pub use crate::error::{HdError, Result};
+pub use crate::geometry::Geometry;
+pub use crate::instruction::Instruction;
+pub use crate::lcd::Lcd;
+pub use crate::marquee::{Marquee, ScrollStyle};
+pub use crate::menu::{Menu, MenuEvent, MenuItem, MenuResponse};
+pub use crate::render::{CharRom, Image, Renderer, Theme};
+pub use crate::sparkline::Sparkline;
@@ -0,0 +1,305 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Scrolls text that is too long for the display without blocking.
+
+use crate::{FunctionMode, Lcd, Result, ShiftMode, HD44780};
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// How the text moves once its end has been shown.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ScrollStyle {
+    /// Keep going left with the start of the text following the end.
+    #[default]
+    Wrap,
+    /// Go back right to the start of the text and then left again.
+    Bounce,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Mode {
+    /// Shift the whole display.
+    Hardware,
+    /// Re-write part of one row.
+    Line { col: u8, width: u8 },
+}
+
+/// Scrolls a message across the display one column at a time.
+///
+/// ## Remarks
+///
+/// The marquee never sleeps, instead [tick()] is called from the main loop
+/// with the current time and moves the text when the next step is due.
+/// [next_tick()] gives when that will be for loops that want to sleep.
+///
+/// A marquee from [hardware()] uses the display shift command so the whole
+/// display moves together, which is fast as only one command is sent for each
+/// step, but every row scrolls.
+/// The text is limited to one DD RAM line (40 characters in 2 line mode)
+/// and wraps around with the line.
+/// On 4 row displays rows 2 and 3 are part of the same lines as rows 0 and 1
+/// so they scroll with them.
+/// Text that needs to scroll is written over the whole DD RAM line its row is
+/// part of, so on 4 row displays the row sharing that line is overwritten.
+///
+/// A marquee from [line()] re-writes its part of one row for each step so
+/// everything else on the display stays still.
+///
+/// Text that fits without scrolling is drawn once, padded with spaces to the
+/// width it is shown in, and left alone.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let mut marquee = Marquee::line(0, 1, 16, "Watch me move right and then left!");
+/// marquee.set_style(ScrollStyle::Bounce);
+/// loop {
+///     marquee.tick(&mut lcd, Instant::now())?;
+///     // Do other work.
+/// }
+/// ```
+///
+/// [tick()]: #method.tick
+/// [next_tick()]: #method.next_tick
+/// [hardware()]: #method.hardware
+/// [line()]: #method.line
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Marquee {
+    text: Vec<u8>,
+    row: u8,
+    mode: Mode,
+    style: ScrollStyle,
+    step: Duration,
+    pause: Duration,
+    gap: u8,
+    offset: usize,
+    backwards: bool,
+    /// When the next step is due, `None` until the text has been drawn.
+    next: Option<Instant>,
+}
+
+impl Marquee {
+    /// Create a marquee that shifts the whole display.
+    ///
+    /// ## Arguments
+    /// * `row` - Row the text is written on.
+    /// * `text` - Character codes to show, cut to the length of a DD RAM line.
+    pub fn hardware<T: AsRef<[u8]>>(row: u8, text: T) -> Self {
+        Self::with_mode(Mode::Hardware, row, text)
+    }
+    /// Create a marquee that only scrolls part of one row.
+    ///
+    /// ## Arguments
+    /// * `col` - Column of the left side of the scrolling area.
+    /// * `row` - Row of the scrolling area.
+    /// * `width` - Number of columns the text is shown in.
+    /// * `text` - Character codes to show.
+    pub fn line<T: AsRef<[u8]>>(col: u8, row: u8, width: u8, text: T) -> Self {
+        Self::with_mode(Mode::Line { col, width }, row, text)
+    }
+    fn with_mode<T: AsRef<[u8]>>(mode: Mode, row: u8, text: T) -> Self {
+        Marquee {
+            text: text.as_ref().to_vec(),
+            row,
+            mode,
+            style: ScrollStyle::default(),
+            step: Duration::from_millis(300),
+            pause: Duration::from_secs(1),
+            gap: 4,
+            offset: 0,
+            backwards: false,
+            next: None,
+        }
+    }
+    /// Change between wrapping and bouncing, defaults to wrapping.
+    pub fn set_style(&mut self, style: ScrollStyle) {
+        self.style = style;
+        self.reset();
+    }
+    /// Change the time between steps, defaults to 300ms.
+    pub fn set_step(&mut self, step: Duration) {
+        self.step = step;
+    }
+    /// Change how long the text stays still at its ends, defaults to 1s.
+    ///
+    /// Wrapping text only pauses at its start.
+    pub fn set_pause(&mut self, pause: Duration) {
+        self.pause = pause;
+    }
+    /// Change the number of spaces between the end and start of wrapping text
+    /// in a [line()] marquee, defaults to 4.
+    ///
+    /// [line()]: #method.line
+    ///
+    pub fn set_gap(&mut self, gap: u8) {
+        self.gap = gap;
+        self.reset();
+    }
+    /// How many columns the text has been moved left.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+    /// When the next step is due if the text has been drawn.
+    pub fn next_tick(&self) -> Option<Instant> {
+        self.next
+    }
+    /// Start over from the beginning of the text on the next tick.
+    pub fn reset(&mut self) {
+        self.offset = 0;
+        self.backwards = false;
+        self.next = None;
+    }
+    /// Draw or move the text if it is time to.
+    ///
+    /// The first tick after creating or resetting the marquee draws the text.
+    ///
+    /// ## Arguments
+    /// * `lcd` - Display to draw on.
+    /// * `now` - The current time.
+    ///
+    /// Returns `true` if anything was sent to the display.
+    pub fn tick<D>(&mut self, lcd: &mut Lcd<D>, now: Instant) -> Result<bool>
+    where
+        D: HD44780,
+    {
+        let window = self.window(lcd);
+        let len = self.text_len(lcd);
+        match self.next {
+            None => {
+                self.start(lcd)?;
+                self.next = Some(now + self.pause);
+                return Ok(true);
+            }
+            Some(next) if now < next || len <= window => return Ok(false),
+            Some(_) => {}
+        }
+        let left = ShiftMode::DISPLAY_MOVE | ShiftMode::MOVE_LEFT;
+        let right = ShiftMode::DISPLAY_MOVE | ShiftMode::MOVE_RIGHT;
+        let (shift, at_end) = match self.style {
+            ScrollStyle::Wrap => {
+                self.offset = (self.offset + 1) % self.cycle(lcd);
+                (left, self.offset == 0)
+            }
+            ScrollStyle::Bounce => {
+                let last = len - window;
+                let shift = if self.backwards {
+                    self.offset -= 1;
+                    right
+                } else {
+                    self.offset += 1;
+                    left
+                };
+                if self.offset == last || self.offset == 0 {
+                    self.backwards = self.offset != 0;
+                    (shift, true)
+                } else {
+                    (shift, false)
+                }
+            }
+        };
+        match self.mode {
+            Mode::Hardware => lcd.cursor_shift(shift)?,
+            Mode::Line { .. } => self.draw(lcd)?,
+        }
+        self.next = Some(now + if at_end { self.pause } else { self.step });
+        Ok(true)
+    }
+    /// Number of columns the text is shown in.
+    fn window<D: HD44780>(&self, lcd: &Lcd<D>) -> usize {
+        match self.mode {
+            Mode::Hardware => lcd.geometry().columns as usize,
+            Mode::Line { width, .. } => width as usize,
+        }
+    }
+    /// Length of the text that can be shown.
+    fn text_len<D: HD44780>(&self, lcd: &Lcd<D>) -> usize {
+        match self.mode {
+            Mode::Hardware => self.text.len().min(line_len(lcd)),
+            Mode::Line { .. } => self.text.len(),
+        }
+    }
+    /// Number of steps before wrapping text is back at its start.
+    fn cycle<D: HD44780>(&self, lcd: &Lcd<D>) -> usize {
+        match self.mode {
+            Mode::Hardware => line_len(lcd),
+            Mode::Line { .. } => self.text.len() + self.gap as usize,
+        }
+    }
+    fn start<D: HD44780>(&mut self, lcd: &mut Lcd<D>) -> Result {
+        match self.mode {
+            Mode::Hardware if self.text.len() <= self.window(lcd) => {
+                let mut visible = self.text.clone();
+                visible.resize(self.window(lcd), b' ');
+                lcd.set_cursor(0, self.row)?;
+                lcd.write_all(&visible)?;
+                Ok(())
+            }
+            Mode::Hardware => {
+                // Undo any earlier shifting and fill the whole line from its
+                // start so the address counter doesn't run on into the next one.
+                lcd.return_home()?;
+                let start = lcd.geometry().address(0, self.row)?;
+                let base = if lcd.state().function_mode().contains(FunctionMode::LINES_2) {
+                    start & 0b1100_0000
+                } else {
+                    start & 0b1000_0000
+                };
+                let len = line_len(lcd);
+                let at = (start - base) as usize;
+                let mut line = vec![b' '; len];
+                for (i, &byte) in self.text.iter().take(len).enumerate() {
+                    line[(at + i) % len] = byte;
+                }
+                lcd.set_dd_ram_addr(base)?;
+                lcd.write_all(&line)?;
+                Ok(())
+            }
+            Mode::Line { .. } => self.draw(lcd),
+        }
+    }
+    /// Re-write the visible part of the text for line mode.
+    fn draw<D: HD44780>(&self, lcd: &mut Lcd<D>) -> Result {
+        if let Mode::Line { col, width } = self.mode {
+            let cycle = self.text.len() + self.gap as usize;
+            let visible: Vec<u8> = (self.offset..self.offset + width as usize)
+                .map(|i| match self.style {
+                    ScrollStyle::Wrap if self.text.len() > width as usize => {
+                        *self.text.get(i % cycle).unwrap_or(&b' ')
+                    }
+                    _ => *self.text.get(i).unwrap_or(&b' '),
+                })
+                .collect();
+            lcd.set_cursor(col, self.row)?;
+            lcd.write_all(&visible)?;
+        }
+        Ok(())
+    }
+}
+
+/// Number of characters in each DD RAM line for the current line mode.
+fn line_len<D: HD44780>(lcd: &Lcd<D>) -> usize {
+    if lcd.state().function_mode().contains(FunctionMode::LINES_2) {
+        40
+    } else {
+        80
+    }
+}
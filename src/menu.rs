@@ -0,0 +1,438 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! A settings menu made of submenus, toggles, numbers, and actions.
+
+use crate::cg_ram_manager::code;
+use crate::{Buttons, Charset, CustomChar, Key, Lcd, Result, HD44780};
+use std::io::Write;
+
+/// Arrow pointing at the selected item.
+const MARKER: [u8; 8] = [0x00, 0x10, 0x18, 0x1C, 0x18, 0x10, 0x00, 0x00];
+/// Shown when there are items above the top row.
+const MORE_UP: [u8; 8] = [0x04, 0x0E, 0x1F, 0x00, 0x00, 0x00, 0x00, 0x00];
+/// Shown when there are items below the bottom row.
+const MORE_DOWN: [u8; 8] = [0x00, 0x00, 0x00, 0x00, 0x1F, 0x0E, 0x04, 0x00];
+
+/// Input used to move around a menu.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MenuEvent {
+    /// Select the item above or increase a number being edited.
+    Up,
+    /// Select the item below or decrease a number being edited.
+    Down,
+    /// Use the selected item or finish editing a number.
+    Select,
+    /// Go back to the parent menu or finish editing a number.
+    Back,
+}
+
+impl MenuEvent {
+    /// Menu event for a key from a [TerminalDriver] if it has one.
+    ///
+    /// `Enter` and `Right` select while `Escape`, `Backspace`, and `Left` go
+    /// back.
+    ///
+    /// [TerminalDriver]: struct.TerminalDriver.html
+    ///
+    pub fn from_key(key: Key) -> Option<Self> {
+        match key {
+            Key::Up => Some(MenuEvent::Up),
+            Key::Down => Some(MenuEvent::Down),
+            Key::Enter | Key::Right => Some(MenuEvent::Select),
+            Key::Escape | Key::Backspace | Key::Left => Some(MenuEvent::Back),
+            Key::Char(_) => None,
+        }
+    }
+    /// Menu event for the pressed buttons of an [I2cMcp230xxDriver] if any.
+    ///
+    /// `SELECT` and `RIGHT` select while `LEFT` goes back.
+    /// Only one event is returned when several buttons are pressed with `UP`
+    /// being checked first, then `DOWN`, select, and back.
+    ///
+    /// [I2cMcp230xxDriver]: struct.I2cMcp230xxDriver.html
+    ///
+    pub fn from_buttons(buttons: Buttons) -> Option<Self> {
+        if buttons.contains(Buttons::UP) {
+            Some(MenuEvent::Up)
+        } else if buttons.contains(Buttons::DOWN) {
+            Some(MenuEvent::Down)
+        } else if buttons.intersects(Buttons::SELECT | Buttons::RIGHT) {
+            Some(MenuEvent::Select)
+        } else if buttons.contains(Buttons::LEFT) {
+            Some(MenuEvent::Back)
+        } else {
+            None
+        }
+    }
+}
+
+/// One entry in a menu.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MenuItem {
+    /// Opens another list of items.
+    Submenu { label: String, items: Vec<MenuItem> },
+    /// An on or off setting flipped by selecting it.
+    Toggle { label: String, value: bool },
+    /// A whole number setting changed with up and down after selecting it.
+    Number {
+        label: String,
+        value: i32,
+        min: i32,
+        max: i32,
+        step: i32,
+    },
+    /// Something for the application to do when selected.
+    Action { label: String },
+}
+
+impl MenuItem {
+    /// Create a submenu item.
+    pub fn submenu<L: Into<String>>(label: L, items: Vec<MenuItem>) -> Self {
+        MenuItem::Submenu {
+            label: label.into(),
+            items,
+        }
+    }
+    /// Create a toggle item.
+    pub fn toggle<L: Into<String>>(label: L, value: bool) -> Self {
+        MenuItem::Toggle {
+            label: label.into(),
+            value,
+        }
+    }
+    /// Create a number item which is kept between `min` and `max`.
+    ///
+    /// The limits are swapped if `min` is larger than `max` and a negative or
+    /// zero `step` is made positive so up always increases the value.
+    pub fn number<L: Into<String>>(label: L, value: i32, min: i32, max: i32, step: i32) -> Self {
+        let (min, max) = if min > max { (max, min) } else { (min, max) };
+        MenuItem::Number {
+            label: label.into(),
+            value: value.clamp(min, max),
+            min,
+            max,
+            step: step.saturating_abs().max(1),
+        }
+    }
+    /// Create an action item.
+    pub fn action<L: Into<String>>(label: L) -> Self {
+        MenuItem::Action {
+            label: label.into(),
+        }
+    }
+    /// Text shown for the item.
+    pub fn label(&self) -> &str {
+        match self {
+            MenuItem::Submenu { label, .. }
+            | MenuItem::Toggle { label, .. }
+            | MenuItem::Number { label, .. }
+            | MenuItem::Action { label } => label,
+        }
+    }
+}
+
+/// What happened because of a menu event that the application may care about.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MenuResponse {
+    /// An action item with the given label was selected.
+    Action(String),
+    /// A toggle item with the given label was changed to the value.
+    Toggled(String, bool),
+    /// A number item with the given label was edited to the value.
+    Changed(String, i32),
+    /// Back was used in the top level menu.
+    Exit,
+}
+
+/// A tree of menu items shown on and controlled from the display.
+///
+/// ## Remarks
+///
+/// Each row of the display shows one item of the current menu with an arrow
+/// custom character in the first column pointing at the selected item.
+/// Values are shown on the right side of the row and the last column has
+/// custom character arrows when there are more items above or below.
+/// The three custom characters are put into CG RAM slots starting at
+/// [set_first_slot()] (slot 0 by default) by [load()].
+/// With the 5x10 font only slots 0 to 3 exist so the first slot can be at most
+/// 1.
+///
+/// The menu uses the number of rows and columns from the [Lcd] geometry so
+/// the same menu works on any size of display.
+///
+/// Events can come from anything, [MenuEvent::from_key()] and
+/// [MenuEvent::from_buttons()] cover the terminal driver and I²C button
+/// boards.
+/// [render()] needs to be called after [handle()] to show any changes.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let mut menu = Menu::new(vec![
+///     MenuItem::submenu("Display", vec![
+///         MenuItem::toggle("Backlight", true),
+///         MenuItem::number("Contrast", 5, 0, 10, 1),
+///     ]),
+///     MenuItem::action("Reboot"),
+/// ]);
+/// menu.load(&mut lcd)?;
+/// menu.render(&mut lcd)?;
+/// if let Some(event) = MenuEvent::from_buttons(driver.buttons()?) {
+///     if let Some(MenuResponse::Action(label)) = menu.handle(event) {
+///         // Do the action.
+///     }
+///     menu.render(&mut lcd)?;
+/// }
+/// ```
+///
+/// [set_first_slot()]: #method.set_first_slot
+/// [load()]: #method.load
+/// [Lcd]: struct.Lcd.html
+/// [MenuEvent::from_key()]: enum.MenuEvent.html#method.from_key
+/// [MenuEvent::from_buttons()]: enum.MenuEvent.html#method.from_buttons
+/// [render()]: #method.render
+/// [handle()]: #method.handle
+///
+#[derive(Clone, Debug)]
+pub struct Menu {
+    items: Vec<MenuItem>,
+    /// Index of each submenu entered from the top level down.
+    path: Vec<usize>,
+    /// Selected item on each level including the current one.
+    selected: Vec<usize>,
+    /// Index of the item on the top row.
+    top: usize,
+    editing: bool,
+    first_slot: u8,
+    charset: Charset,
+}
+
+impl Menu {
+    /// Create a new menu with the top level items.
+    pub fn new(items: Vec<MenuItem>) -> Self {
+        Menu {
+            items,
+            path: Vec::new(),
+            selected: vec![0],
+            top: 0,
+            editing: false,
+            first_slot: 0,
+            charset: Charset::default(),
+        }
+    }
+    /// Change the first of the 3 CG RAM slots used by the menu.
+    ///
+    /// Must be called before [load()].
+    ///
+    /// [load()]: #method.load
+    ///
+    pub fn set_first_slot(&mut self, slot: u8) {
+        self.first_slot = slot;
+    }
+    /// Change how labels are encoded, defaults to the A00 character ROM.
+    pub fn set_charset(&mut self, charset: Charset) {
+        self.charset = charset;
+    }
+    /// The top level items with their current values.
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+    /// The item currently selected.
+    pub fn selected(&self) -> Option<&MenuItem> {
+        self.level().get(self.index())
+    }
+    /// How many submenus deep the current menu is.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+    /// If a number item is being edited.
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+    /// Load the custom characters needed by the menu.
+    pub fn load<D>(&self, lcd: &mut Lcd<D>) -> Result
+    where
+        D: HD44780,
+    {
+        for (offset, rows) in [MARKER, MORE_UP, MORE_DOWN].iter().enumerate() {
+            let slot = self.first_slot.saturating_add(offset as u8);
+            lcd.load_custom_char(slot, &CustomChar::new(*rows))?;
+        }
+        Ok(())
+    }
+    /// Move around the menu or change the selected item.
+    ///
+    /// Returns what changed if the application needs to know about it.
+    pub fn handle(&mut self, event: MenuEvent) -> Option<MenuResponse> {
+        let index = self.index();
+        let len = self.level().len();
+        if self.editing {
+            if let Some(MenuItem::Number {
+                label,
+                value,
+                min,
+                max,
+                step,
+            }) = self.level_mut().get_mut(index)
+            {
+                match event {
+                    MenuEvent::Up => *value = value.saturating_add(*step).min(*max),
+                    MenuEvent::Down => *value = value.saturating_sub(*step).max(*min),
+                    MenuEvent::Select | MenuEvent::Back => {
+                        let response = MenuResponse::Changed(label.clone(), *value);
+                        self.editing = false;
+                        return Some(response);
+                    }
+                }
+            }
+            return None;
+        }
+        match event {
+            MenuEvent::Up => {
+                self.set_index(index.saturating_sub(1));
+                None
+            }
+            MenuEvent::Down => {
+                if index + 1 < len {
+                    self.set_index(index + 1);
+                }
+                None
+            }
+            MenuEvent::Back => {
+                if self.path.pop().is_some() {
+                    self.selected.pop();
+                    self.top = 0;
+                    None
+                } else {
+                    Some(MenuResponse::Exit)
+                }
+            }
+            MenuEvent::Select => match self.level_mut().get_mut(index)? {
+                MenuItem::Submenu { items, .. } => {
+                    if !items.is_empty() {
+                        self.path.push(index);
+                        self.selected.push(0);
+                        self.top = 0;
+                    }
+                    None
+                }
+                MenuItem::Toggle { label, value } => {
+                    *value = !*value;
+                    Some(MenuResponse::Toggled(label.clone(), *value))
+                }
+                MenuItem::Number { .. } => {
+                    self.editing = true;
+                    None
+                }
+                MenuItem::Action { label } => Some(MenuResponse::Action(label.clone())),
+            },
+        }
+    }
+    /// Draw the current menu over the whole display.
+    pub fn render<D>(&mut self, lcd: &mut Lcd<D>) -> Result
+    where
+        D: HD44780,
+    {
+        let geometry = *lcd.geometry();
+        let rows = geometry.rows as usize;
+        let columns = geometry.columns as usize;
+        let tall = lcd.state().is_5x10();
+        let index = self.index();
+        // Keep the selected item on the display.
+        if index < self.top {
+            self.top = index;
+        } else if index >= self.top + rows {
+            self.top = index + 1 - rows;
+        }
+        let level = self.level();
+        // Space between the marker and scroll arrow columns.
+        let area = columns.saturating_sub(2);
+        for row in 0..rows {
+            let mut line = vec![b' '; columns];
+            let i = self.top + row;
+            if let Some(item) = level.get(i) {
+                if i == index {
+                    line[0] = code(self.first_slot, tall);
+                }
+                let value = self.value(item, i == index && self.editing);
+                let value_len = value.len().min(area);
+                let label = self.charset.encode(item.label())?;
+                let label_len = if value_len == 0 {
+                    label.len().min(area)
+                } else {
+                    label.len().min(area.saturating_sub(value_len + 1))
+                };
+                line[1..1 + label_len].copy_from_slice(&label[..label_len]);
+                line[1 + area - value_len..1 + area].copy_from_slice(&value[..value_len]);
+            }
+            if columns > 1 {
+                if row == 0 && self.top > 0 {
+                    line[columns - 1] = code(self.first_slot.saturating_add(1), tall);
+                } else if row == rows - 1 && self.top + rows < level.len() {
+                    line[columns - 1] = code(self.first_slot.saturating_add(2), tall);
+                }
+            }
+            lcd.set_cursor(0, row as u8)?;
+            lcd.write_all(&line)?;
+        }
+        Ok(())
+    }
+    /// Text shown on the right side for an item.
+    fn value(&self, item: &MenuItem, editing: bool) -> Vec<u8> {
+        match item {
+            MenuItem::Submenu { .. } => b">".to_vec(),
+            MenuItem::Toggle { value: true, .. } => b"On".to_vec(),
+            MenuItem::Toggle { value: false, .. } => b"Off".to_vec(),
+            MenuItem::Number { value, .. } if editing => format!("<{}>", value).into_bytes(),
+            MenuItem::Number { value, .. } => value.to_string().into_bytes(),
+            MenuItem::Action { .. } => Vec::new(),
+        }
+    }
+    fn index(&self) -> usize {
+        self.selected.last().copied().unwrap_or_default()
+    }
+    fn set_index(&mut self, index: usize) {
+        if let Some(selected) = self.selected.last_mut() {
+            *selected = index;
+        }
+    }
+    /// Items of the current menu.
+    fn level(&self) -> &[MenuItem] {
+        let mut items = &self.items;
+        for &i in &self.path {
+            if let Some(MenuItem::Submenu { items: sub, .. }) = items.get(i) {
+                items = sub;
+            }
+        }
+        items
+    }
+    fn level_mut(&mut self) -> &mut Vec<MenuItem> {
+        let mut items = &mut self.items;
+        for &i in &self.path {
+            items = match &mut items[i] {
+                MenuItem::Submenu { items, .. } => items,
+                // Only submenus are ever added to the path.
+                _ => unreachable!(),
+            };
+        }
+        items
+    }
+}
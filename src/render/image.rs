@@ -0,0 +1,170 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Minimal RGB image type with PPM and PNG encoding.
+
+use std::convert::TryFrom;
+
+// Largest amount of data in a stored (uncompressed) deflate block.
+const MAX_STORED_BLOCK: usize = 0xFFFF;
+
+/// An 8 bit per channel RGB image made by [Renderer].
+///
+/// [Renderer]: struct.Renderer.html
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Image {
+    pub(crate) fn new(width: u32, height: u32, color: [u8; 3]) -> Self {
+        let data = color
+            .iter()
+            .copied()
+            .cycle()
+            .take(width as usize * height as usize * 3)
+            .collect();
+        Image {
+            width,
+            height,
+            data,
+        }
+    }
+    /// Width of the image in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    /// Height of the image in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    /// Color of the pixel at `x`, `y`.
+    ///
+    /// ## Panics
+    /// If `x` or `y` are outside of the image.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        assert!(x < self.width && y < self.height, "Pixel outside of image");
+        let i = self.index(x, y);
+        [self.data[i], self.data[i + 1], self.data[i + 2]]
+    }
+    /// Raw RGB bytes of the image one row after another.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+    /// Encode image as a binary (P6) PPM file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend_from_slice(&self.data);
+        ppm
+    }
+    /// Encode image as a PNG file.
+    ///
+    /// The image data is stored without compression which keeps the encoder
+    /// small and the files are still readable by anything that reads PNGs.
+    pub fn to_png(&self) -> Vec<u8> {
+        let row_len = self.width as usize * 3;
+        // Each scan line starts with filter type 0 (none).
+        let mut raw = Vec::with_capacity((row_len + 1) * self.height as usize);
+        for row in self.data.chunks(row_len.max(1)) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&self.width.to_be_bytes());
+        ihdr.extend_from_slice(&self.height.to_be_bytes());
+        // 8 bit depth, RGB color type, default compression, filter, and no
+        // interlace.
+        ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        write_chunk(&mut png, b"IHDR", &ihdr);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+    pub(crate) fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        for py in y..(y + height).min(self.height) {
+            for px in x..(x + width).min(self.width) {
+                let i = self.index(px, py);
+                self.data[i..i + 3].copy_from_slice(&color);
+            }
+        }
+    }
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 3
+    }
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let len = u32::try_from(data.len()).expect("PNG chunk too large");
+    png.extend_from_slice(&len.to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wrap data in a zlib stream using only stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // Deflate with 32K window and no preset dictionary.
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[0x01, 0x00, 0x00, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none() as u8;
+        let len = block.len() as u16;
+        out.push(last);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
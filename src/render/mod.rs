@@ -0,0 +1,236 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Renders what a HD44780 display shows as images.
+
+pub use self::image::Image;
+use crate::{DisplayMode, EmulatorDriver};
+
+mod font;
+mod image;
+
+/// Which character ROM the display controller has.
+///
+/// Most displays sold are the A00 (Japanese) version which has katakana in the
+/// upper half while the A02 (European) version has accented Latin characters
+/// instead.
+/// The lower half is ASCII except A00 has `¥`, `→`, and `←` in place of `\`,
+/// `~`, and `DEL`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum CharRom {
+    #[default]
+    A00,
+    A02,
+}
+
+impl CharRom {
+    /// Pixel rows of ROM character `code`.
+    ///
+    /// Codes `0x00`-`0x0F` are the CG RAM characters and have nothing in ROM
+    /// so like all other unused codes they are blank. Only A02 has glyphs for
+    /// `0x10`-`0x1F`.
+    pub fn glyph(self, code: u8) -> [u8; 10] {
+        match (self, code) {
+            (CharRom::A02, 0x10..=0x1F) => font::A02_LOW[(code - 0x10) as usize],
+            (_, 0x00..=0x1F) => [0; 10],
+            (CharRom::A00, _) => font::A00[(code - 0x20) as usize],
+            (CharRom::A02, _) => font::A02[(code - 0x20) as usize],
+        }
+    }
+}
+
+/// Colors used when rendering a display.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Theme {
+    /// Color of the glass around and between the characters.
+    pub background: [u8; 3],
+    /// Color of pixels that are on.
+    pub pixel_on: [u8; 3],
+    /// Color of pixels that are off, usually just a little different than the
+    /// background so the dot matrix can be seen.
+    pub pixel_off: [u8; 3],
+}
+
+impl Theme {
+    /// Dark pixels on a yellow-green backlight.
+    pub const GREEN: Theme = Theme {
+        background: [0x9C, 0xC2, 0x2E],
+        pixel_on: [0x1F, 0x2B, 0x0C],
+        pixel_off: [0x8E, 0xB3, 0x28],
+    };
+    /// White pixels on a blue backlight.
+    pub const BLUE: Theme = Theme {
+        background: [0x1D, 0x3F, 0xD8],
+        pixel_on: [0xEE, 0xF4, 0xFF],
+        pixel_off: [0x27, 0x4B, 0xE4],
+    };
+    /// Amber pixels on a dark background.
+    pub const AMBER: Theme = Theme {
+        background: [0x1A, 0x10, 0x02],
+        pixel_on: [0xFF, 0xB0, 0x00],
+        pixel_off: [0x2A, 0x1C, 0x06],
+    };
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::GREEN
+    }
+}
+
+/// Turns the state of an [EmulatorDriver] into a pixel accurate image.
+///
+/// ## Remarks
+///
+/// Each dot of the display is drawn as a square `pixel_size` wide with a
+/// `pixel_gap` between dots.
+/// There is a one dot space between characters and rows, and a two dot
+/// border around the display.
+///
+/// When [FunctionMode::DOTS_5X10] is used in 1 line mode the characters are 10
+/// dots high (plus the cursor line) and CG RAM has 4 custom characters of 16
+/// bytes each, the same way the controller does.
+///
+/// The cursor is drawn on the bottom line of the character and the blinking
+/// block cursor is always drawn in its on state.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let mut lcd = EmulatorDriver::new(16, 2);
+/// lcd.init(FunctionMode::LINES_2, DisplayMode::DISPLAY_ON, None)?;
+/// write!(lcd, "Hello")?;
+/// let mut renderer = Renderer::new(CharRom::A00);
+/// renderer.set_theme(Theme::BLUE);
+/// std::fs::write("hello.png", renderer.render(&lcd).to_png())?;
+/// ```
+///
+/// [EmulatorDriver]: struct.EmulatorDriver.html
+/// [FunctionMode::DOTS_5X10]: struct.FunctionMode.html#associatedconstant.DOTS_5X10
+///
+#[derive(Clone, Debug)]
+pub struct Renderer {
+    rom: CharRom,
+    theme: Theme,
+    pixel_size: u32,
+    pixel_gap: u32,
+}
+
+impl Renderer {
+    /// Create a new renderer using the default green theme with 3 pixel dots
+    /// and 1 pixel gaps.
+    ///
+    /// ## Arguments
+    /// * `rom` - Which character ROM to use for non-custom characters.
+    pub fn new(rom: CharRom) -> Self {
+        Renderer {
+            rom,
+            theme: Theme::default(),
+            pixel_size: 3,
+            pixel_gap: 1,
+        }
+    }
+    /// Change the colors used.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+    /// Change how large each dot is and the gap between them in pixels.
+    ///
+    /// A `size` of 0 is treated as 1.
+    pub fn set_pixel_size(&mut self, size: u32, gap: u32) {
+        self.pixel_size = size.max(1);
+        self.pixel_gap = gap;
+    }
+    /// Render the visible window of the display.
+    ///
+    /// ## Arguments
+    /// * `lcd` - Emulated display that has been sent the commands and data.
+    pub fn render(&self, lcd: &EmulatorDriver) -> Image {
+        let tall = lcd.is_5x10();
+        let cell_height: u32 = if tall { 11 } else { 8 };
+        let pitch = self.pixel_size + self.pixel_gap;
+        let margin = 2 * pitch;
+        let columns = lcd.columns() as u32;
+        let rows = lcd.rows() as u32;
+        let width = 2 * margin + (columns * 6).saturating_sub(1) * pitch;
+        let height = 2 * margin + (rows * (cell_height + 1)).saturating_sub(1) * pitch;
+        let mut image = Image::new(width, height, self.theme.background);
+        let mode = lcd.display_mode();
+        let on = mode.contains(DisplayMode::DISPLAY_ON);
+        let cursor = if on { lcd.cursor() } else { None };
+        for (row, glyphs) in lcd.glyphs().iter().enumerate() {
+            for (col, &code) in glyphs.iter().enumerate() {
+                let mut pattern = if on {
+                    self.pattern(lcd, code, tall)
+                } else {
+                    [0; 11]
+                };
+                if cursor == Some((col as u8, row as u8)) {
+                    if mode.contains(DisplayMode::CURSOR_ON) {
+                        pattern[cell_height as usize - 1] = 0x1F;
+                    }
+                    if mode.contains(DisplayMode::BLINK_ON) {
+                        pattern = [0x1F; 11];
+                    }
+                }
+                let left = margin + col as u32 * 6 * pitch;
+                let top = margin + row as u32 * (cell_height + 1) * pitch;
+                for (y, bits) in pattern.iter().take(cell_height as usize).enumerate() {
+                    for x in 0..5 {
+                        let color = if bits & (0x10 >> x) != 0 {
+                            self.theme.pixel_on
+                        } else {
+                            self.theme.pixel_off
+                        };
+                        image.fill_rect(
+                            left + x * pitch,
+                            top + y as u32 * pitch,
+                            self.pixel_size,
+                            self.pixel_size,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+        image
+    }
+    /// Pixel rows for a character code including the cursor line.
+    fn pattern(&self, lcd: &EmulatorDriver, code: u8, tall: bool) -> [u8; 11] {
+        let mut pattern = [0; 11];
+        if code < 0x10 {
+            let cg_ram = lcd.cg_ram();
+            if tall {
+                // Only 4 custom characters of 16 bytes with 11 used.
+                let start = ((code as usize >> 1) & 0x03) * 16;
+                pattern.copy_from_slice(&cg_ram[start..start + 11]);
+            } else {
+                let start = (code as usize & 0x07) * 8;
+                pattern[..8].copy_from_slice(&cg_ram[start..start + 8]);
+            }
+        } else {
+            let glyph = self.rom.glyph(code);
+            let len = if tall { 10 } else { 8 };
+            pattern[..len].copy_from_slice(&glyph[..len]);
+        }
+        pattern.iter_mut().for_each(|row| *row &= 0x1F);
+        pattern
+    }
+}
@@ -0,0 +1,234 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Rolling charts of values drawn as vertical bars using custom characters.
+
+use crate::error::HdError::OffScreen;
+use crate::{CustomChar, Lcd, Result, HD44780};
+use std::collections::VecDeque;
+use std::io::Write;
+
+/// Number of bar heights each display row can show.
+const CELL_LEVELS: u8 = 8;
+
+/// A small chart of the most recent values across one or two rows.
+///
+/// ## Remarks
+///
+/// The chart uses all 8 custom characters as bars from 1 to 8 pixels high
+/// which [load()] puts into CG RAM, so it can not share CG RAM with other
+/// widgets and needs the 5x8 font.
+///
+/// The scale is picked from the smallest and largest values being shown so
+/// the smallest value is always a 1 pixel bar and the largest a full one.
+/// When [set_tall()] is used the chart is 2 rows high for 16 levels.
+///
+/// With a label width from [set_label_width()] the largest and smallest values
+/// are shown at the left of the chart, on their own row for tall charts or
+/// on either side of the chart otherwise. A value too wide for its label is
+/// shown as all `#` instead of being cut off.
+///
+/// Values are whole numbers so readings like tenths of a degree should be
+/// scaled before they are pushed.
+///
+/// ## Examples
+/// ```edition2018,ignore
+/// let mut chart = Sparkline::new(0, 0, 16);
+/// chart.set_tall(true);
+/// chart.set_label_width(3);
+/// chart.load(&mut lcd)?;
+/// chart.push(temperature);
+/// chart.draw(&mut lcd)?;
+/// ```
+///
+/// [load()]: #method.load
+/// [set_tall()]: #method.set_tall
+/// [set_label_width()]: #method.set_label_width
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sparkline {
+    col: u8,
+    row: u8,
+    width: u8,
+    tall: bool,
+    label_width: u8,
+    values: VecDeque<i32>,
+}
+
+impl Sparkline {
+    /// Create a new empty chart.
+    ///
+    /// ## Arguments
+    /// * `col` - Column of the left side of the widget.
+    /// * `row` - Top row of the widget.
+    /// * `width` - Number of columns used by the whole widget.
+    pub fn new(col: u8, row: u8, width: u8) -> Self {
+        Sparkline {
+            col,
+            row,
+            width,
+            tall: false,
+            label_width: 0,
+            values: VecDeque::with_capacity(width as usize),
+        }
+    }
+    /// Use two rows for 16 levels instead of one with 8.
+    pub fn set_tall(&mut self, tall: bool) {
+        self.tall = tall;
+        // Labels on either side leave less room on a single row.
+        self.trim();
+    }
+    /// Set how many columns each of the min and max labels use, 0 for none.
+    ///
+    /// Labels are made narrower when needed so they fit in the widget width.
+    pub fn set_label_width(&mut self, width: u8) {
+        self.label_width = width;
+        self.trim();
+    }
+    /// Number of columns used by the bars.
+    pub fn chart_width(&self) -> u8 {
+        let labels = if self.tall {
+            self.label_columns()
+        } else {
+            self.label_columns() * 2
+        };
+        self.width - labels
+    }
+    /// The values being shown from oldest to newest.
+    pub fn values(&self) -> &VecDeque<i32> {
+        &self.values
+    }
+    /// Add a new value, dropping the oldest one if the chart is full.
+    pub fn push(&mut self, value: i32) {
+        self.values.push_back(value);
+        self.trim();
+    }
+    /// Remove all values.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+    /// Load the custom characters needed by the chart.
+    pub fn load<D>(&self, lcd: &mut Lcd<D>) -> Result
+    where
+        D: HD44780,
+    {
+        for slot in 0..CELL_LEVELS {
+            let mut rows = [0; 8];
+            rows[(7 - slot as usize)..]
+                .iter_mut()
+                .for_each(|r| *r = 0x1F);
+            lcd.load_custom_char(slot, &CustomChar::new(rows))?;
+        }
+        Ok(())
+    }
+    /// Draw the chart and labels with the newest value on the right.
+    ///
+    /// ## Errors
+    /// Returns [OffScreen] if the widget does not fit on the display.
+    ///
+    /// [OffScreen]: enum.HdError.html#variant.OffScreen
+    ///
+    pub fn draw<D>(&self, lcd: &mut Lcd<D>) -> Result
+    where
+        D: HD44780,
+    {
+        let geometry = *lcd.geometry();
+        let rows: u8 = if self.tall { 2 } else { 1 };
+        let end = self.col as u16 + self.width as u16;
+        if end > geometry.columns as u16 || self.row as u16 + rows as u16 > geometry.rows as u16 {
+            return Err(OffScreen(
+                (end as u8).wrapping_sub(1),
+                self.row.saturating_add(rows - 1),
+            ));
+        }
+        let min = self.values.iter().copied().min().unwrap_or(0);
+        let max = self.values.iter().copied().max().unwrap_or(0);
+        let levels = CELL_LEVELS * rows;
+        let mut lines = vec![Vec::with_capacity(self.width as usize); rows as usize];
+        let blank = (self.chart_width() as usize).saturating_sub(self.values.len());
+        let mut values = vec![None; blank];
+        values.extend(self.values.iter().map(Some));
+        for value in values {
+            let level = value.map_or(0, |v| level(*v, min, max, levels));
+            // Bottom row first.
+            for (line, offset) in lines
+                .iter_mut()
+                .rev()
+                .zip((0..).step_by(CELL_LEVELS as usize))
+            {
+                line.push(match level.saturating_sub(offset).min(CELL_LEVELS) {
+                    0 => b' ',
+                    height => height - 1,
+                });
+            }
+        }
+        if self.label_columns() > 0 {
+            let (min, max) = (self.label(min), self.label(max));
+            if self.tall {
+                lines[0].splice(..0, max);
+                lines[1].splice(..0, min);
+            } else {
+                lines[0].splice(..0, min);
+                lines[0].extend(max);
+            }
+        }
+        for (offset, line) in lines.iter().enumerate() {
+            lcd.set_cursor(self.col, self.row + offset as u8)?;
+            lcd.write_all(line)?;
+        }
+        Ok(())
+    }
+    /// Value right aligned in the label width or all `#` if it doesn't fit.
+    fn label(&self, value: i32) -> Vec<u8> {
+        let width = self.label_columns() as usize;
+        let text = format!("{:>width$}", value, width = width);
+        if text.len() > width {
+            return vec![b'#'; width];
+        }
+        text.into_bytes()
+    }
+    /// Columns used by each label after narrowing them to fit the widget.
+    fn label_columns(&self) -> u8 {
+        if self.tall {
+            self.label_width.min(self.width)
+        } else {
+            self.label_width.min(self.width / 2)
+        }
+    }
+    fn trim(&mut self) {
+        let width = self.chart_width() as usize;
+        while self.values.len() > width {
+            self.values.pop_front();
+        }
+    }
+}
+
+/// Bar height from 1 to `levels` for a value.
+fn level(value: i32, min: i32, max: i32, levels: u8) -> u8 {
+    let range = max as i64 - min as i64;
+    if range == 0 {
+        return 1;
+    }
+    let steps = (levels - 1) as i64;
+    // Round to the nearest level.
+    let scaled = ((value as i64 - min as i64) * steps * 2 + range) / (range * 2);
+    1 + scaled as u8
+}
@@ -0,0 +1,75 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use hd44780_ntb::{BarGraph, FunctionMode, Geometry, HdError, SpyDriver};
+
+#[test]
+fn update_should_fill_cells_at_fifth_of_a_cell_resolution() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut bar = BarGraph::new(0, 1, 4);
+    bar.load(&mut sut).unwrap();
+    // 9 of 20 pixel columns.
+    bar.update(&mut sut, 45, 100).unwrap();
+    assert_eq!(sut.get_ref().glyphs()[1][..5], [4, 3, b' ', b' ', b' ']);
+    assert_eq!(sut.get_ref().custom_char(0)[..7], [0x10; 7]);
+    assert_eq!(sut.get_ref().custom_char(4)[..7], [0x1F; 7]);
+}
+
+#[test]
+fn update_should_show_caps_and_label() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut bar = BarGraph::new(6, 0, 10);
+    bar.set_caps(true);
+    bar.set_label(true);
+    bar.set_first_slot(3);
+    assert_eq!(bar.bar_width(), 4);
+    bar.update(&mut sut, 150, 100).unwrap();
+    assert_eq!(sut.get_ref().glyphs()[0][6..], *b"[\x07\x07\x07\x07]100%");
+    bar.update(&mut sut, 5, 100).unwrap();
+    assert_eq!(sut.get_ref().glyphs()[0][6..], *b"[\x03   ]  5%");
+}
+
+#[test]
+fn update_should_only_write_changed_cells() {
+    let mut sut = common::lcd(SpyDriver::new(), Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut bar = BarGraph::new(0, 0, 8);
+    bar.update(&mut sut, 0, 40).unwrap();
+    assert_eq!(
+        sut.get_ref().events().last().unwrap().data(),
+        Some(&[b' '; 8][..])
+    );
+    let events = sut.get_ref().events().len();
+    bar.update(&mut sut, 12, 40).unwrap();
+    let new: Vec<&[u8]> = sut.get_ref().events()[events..]
+        .iter()
+        .filter_map(|e| e.data())
+        .collect();
+    assert_eq!(new, [&[4, 4, 1][..]]);
+    bar.update(&mut sut, 12, 40).unwrap();
+    assert_eq!(sut.get_ref().events().len(), events + 2);
+    assert!(matches!(
+        BarGraph::new(10, 0, 7).update(&mut sut, 1, 2),
+        Err(HdError::OffScreen(16, 0))
+    ));
+}
@@ -0,0 +1,65 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use hd44780_ntb::{BigFont, BigFontHeight, FunctionMode, Geometry, HdError};
+
+#[test]
+fn draw_big_text_should_build_digits_from_blocks() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let font = BigFont::new(BigFontHeight::TwoRows);
+    font.load(&mut sut).unwrap();
+    font.draw_big_text(&mut sut, 0, 0, "12").unwrap();
+    let glyphs = sut.get_ref().glyphs();
+    assert_eq!(glyphs[0][..7], [2, 0, b' ', b' ', 3, 3, 0]);
+    assert_eq!(glyphs[1][..7], [2, 0, 2, b' ', 0, 4, 4]);
+    assert_eq!(sut.get_ref().custom_char(0), [0x1F; 8]);
+}
+
+#[test]
+fn draw_big_text_should_use_all_rows_and_handle_punctuation() {
+    let mut sut = common::emulator(Geometry::LCD_20X4, FunctionMode::LINES_2);
+    let font = BigFont::new(BigFontHeight::FourRows);
+    font.draw_big_text(&mut sut, 0, 0, "1:.").unwrap();
+    let glyphs = sut.get_ref().glyphs();
+    let column = |col: usize| -> Vec<u8> { glyphs.iter().map(|row| row[col]).collect() };
+    assert_eq!(column(0), [2, 1, b' ', 1]);
+    assert_eq!(column(1), [0, 0, 0, 1]);
+    assert_eq!(column(4), [2, 1, 0, b' ']);
+    assert_eq!(column(6), [b' ', b' ', b' ', 1]);
+}
+
+#[test]
+fn draw_big_text_should_clip_width_and_reject_rows_off_screen() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let font = BigFont::new(BigFontHeight::TwoRows);
+    assert_eq!(font.text_width("12:34"), 17);
+    font.draw_big_text(&mut sut, 0, 0, "12:34").unwrap();
+    assert!(matches!(
+        font.draw_big_text(&mut sut, 0, 1, "1"),
+        Err(HdError::OffScreen(0, 2))
+    ));
+    assert!(BigFont::new(BigFontHeight::ThreeRows)
+        .draw_big_text(&mut sut, 0, 0, "1")
+        .is_err());
+}
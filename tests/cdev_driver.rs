@@ -0,0 +1,95 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{CdevDriver, HdError, LineHandle, Result, VirtualClock, HD44780};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// In-memory stand in for a requested set of GPIO lines.
+#[derive(Clone)]
+struct FakeLines {
+    count: usize,
+    calls: Rc<RefCell<Vec<Vec<u8>>>>,
+}
+
+impl FakeLines {
+    fn new(count: usize) -> Self {
+        FakeLines {
+            count,
+            calls: Rc::default(),
+        }
+    }
+}
+
+impl LineHandle for FakeLines {
+    fn num_lines(&self) -> usize {
+        self.count
+    }
+    fn set_values(&mut self, values: &[u8]) -> Result {
+        self.calls.borrow_mut().push(Vec::from(values));
+        Ok(())
+    }
+}
+
+#[test]
+fn command_should_set_whole_bus_with_one_call_per_nibble_edge() {
+    let lines = FakeLines::new(6);
+    let mut sut = CdevDriver::new(lines.clone(), VirtualClock::new()).unwrap();
+    sut.set_dd_ram_addr(0x45).unwrap();
+    // RS low, then 0xC5 as two nibbles with enable high then low, then RS high.
+    assert_eq!(
+        *lines.calls.borrow(),
+        vec![
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 1, 0, 0, 1, 1],
+            vec![0, 0, 0, 0, 1, 1],
+            vec![0, 1, 1, 0, 1, 0],
+            vec![0, 0, 1, 0, 1, 0],
+            vec![1, 0, 1, 0, 1, 0],
+        ]
+    );
+}
+
+#[test]
+fn write_should_use_8_bit_bus_when_given_8_data_lines() {
+    let lines = FakeLines::new(10);
+    let mut sut = CdevDriver::new(lines.clone(), VirtualClock::new()).unwrap();
+    sut.write_all(b"A").unwrap();
+    assert_eq!(
+        *lines.calls.borrow(),
+        vec![
+            vec![1, 1, 1, 0, 0, 0, 0, 0, 1, 0],
+            vec![1, 0, 1, 0, 0, 0, 0, 0, 1, 0]
+        ]
+    );
+}
+
+#[test]
+fn new_should_return_error_for_wrong_line_count() {
+    for count in [0, 2, 5, 8, 11].iter() {
+        assert!(matches!(
+            CdevDriver::new(FakeLines::new(*count), VirtualClock::new()),
+            Err(HdError::InvalidDataBusLen)
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use hd44780_ntb::{CgRamManager, CustomChar, FunctionMode, Geometry, HdError};
+use std::io::Write;
+
+fn char(n: u8) -> CustomChar {
+    CustomChar::new([n; 8])
+}
+
+#[test]
+fn glyph_should_reuse_loaded_characters() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut cg_ram = CgRamManager::new();
+    assert_eq!(cg_ram.glyph(&mut sut, &char(1)).unwrap(), 0);
+    assert_eq!(cg_ram.glyph(&mut sut, &char(2)).unwrap(), 1);
+    assert_eq!(cg_ram.glyph(&mut sut, &char(1)).unwrap(), 0);
+    assert_eq!(cg_ram.code_of(&char(2)), Some(1));
+    assert_eq!(cg_ram.code_of(&char(3)), None);
+    assert_eq!(sut.get_ref().custom_char(1), [2; 8]);
+}
+
+#[test]
+fn glyph_should_evict_least_recently_used_off_screen_character() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut cg_ram = CgRamManager::new();
+    for n in 0..8 {
+        let code = cg_ram.glyph(&mut sut, &char(n)).unwrap();
+        // Everything but the last character is left on the display.
+        if n < 7 {
+            sut.write_all(&[code]).unwrap();
+        }
+    }
+    cg_ram.begin_frame();
+    assert_eq!(cg_ram.glyph(&mut sut, &char(10)).unwrap(), 7);
+    assert_eq!(cg_ram.code_of(&char(7)), None);
+    // Once the oldest is drawn over its slot is free again.
+    sut.write_all(&[7]).unwrap();
+    sut.set_cursor(0, 0).unwrap();
+    sut.write_all(b" ").unwrap();
+    assert_eq!(cg_ram.glyph(&mut sut, &char(11)).unwrap(), 0);
+    assert_eq!(sut.get_ref().custom_char(0), [11; 8]);
+}
+
+#[test]
+fn glyph_should_not_evict_characters_still_on_display() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut cg_ram = CgRamManager::new();
+    for n in 0..8 {
+        let code = cg_ram.glyph(&mut sut, &char(n)).unwrap();
+        sut.write_all(&[code]).unwrap();
+    }
+    cg_ram.begin_frame();
+    assert!(matches!(
+        cg_ram.glyph(&mut sut, &char(8)),
+        Err(HdError::CgRamFull(8))
+    ));
+    for n in 0..8 {
+        assert_eq!(sut.get_ref().custom_char(n), [n; 8]);
+    }
+}
+
+#[test]
+fn glyph_should_fail_when_frame_needs_too_many_characters() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut cg_ram = CgRamManager::new();
+    for n in 0..8 {
+        cg_ram.glyph(&mut sut, &char(n)).unwrap();
+    }
+    assert!(matches!(
+        cg_ram.glyph(&mut sut, &char(8)),
+        Err(HdError::CgRamFull(8))
+    ));
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::DOTS_5X10);
+    let codes: Vec<u8> = (0..4)
+        .map(|n| cg_ram.glyph(&mut sut, &char(n)).unwrap())
+        .collect();
+    assert_eq!(codes, [0, 2, 4, 6]);
+    assert!(matches!(
+        cg_ram.glyph(&mut sut, &char(4)),
+        Err(HdError::CgRamFull(4))
+    ));
+}
@@ -0,0 +1,71 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{CharLcdDriver, DisplayMode, EntryMode, FunctionMode, ShiftMode, HD44780};
+use std::io::Write;
+
+fn output(sut: &CharLcdDriver<Vec<u8>>) -> String {
+    String::from_utf8_lossy(sut.get_ref()).into_owned()
+}
+
+#[test]
+fn init_should_reset_then_set_modes() {
+    let mut sut = CharLcdDriver::new(Vec::new());
+    let dc = DisplayMode::DISPLAY_ON | DisplayMode::BLINK_ON;
+    sut.init(FunctionMode::LINES_2, dc, None).unwrap();
+    assert_eq!(
+        output(&sut),
+        "\x1b[LI\x1b[LN\x1b[Lf\x1b[LD\x1b[Lc\x1b[LB\x1b[2J"
+    );
+}
+
+#[test]
+fn set_dd_ram_addr_should_go_to_kernel_column_and_row() {
+    let mut sut = CharLcdDriver::with_buffer_width(Vec::new(), 20);
+    sut.set_dd_ram_addr(0x05).unwrap();
+    sut.set_dd_ram_addr(0x45).unwrap();
+    sut.set_dd_ram_addr(0x19).unwrap();
+    sut.set_dd_ram_addr(0x59).unwrap();
+    assert_eq!(output(&sut), "\x1b[Lx5y0;\x1b[Lx5y1;\x1b[Lx5y2;\x1b[Lx5y3;");
+}
+
+#[test]
+fn cg_ram_writes_should_upload_whole_characters() {
+    let mut sut = CharLcdDriver::new(Vec::new());
+    sut.set_cg_ram_addr(0x08).unwrap();
+    sut.write_all(&[0x1F, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1F, 0x00])
+        .unwrap();
+    sut.set_dd_ram_addr(0x00).unwrap();
+    sut.write_all(&[0x09, b'!']).unwrap();
+    assert_eq!(output(&sut), "\x1b[LG11f11111111111f00;\x1b[Lx0y0;\x01!");
+}
+
+#[test]
+fn shifts_should_use_shift_codes() {
+    let mut sut = CharLcdDriver::new(Vec::new());
+    sut.cursor_shift(ShiftMode::DISPLAY_MOVE | ShiftMode::MOVE_LEFT)
+        .unwrap();
+    sut.cursor_shift(ShiftMode::CURSOR_MOVE | ShiftMode::MOVE_RIGHT)
+        .unwrap();
+    assert_eq!(output(&sut), "\x1b[LL\x1b[Lr");
+    assert!(sut.entry_mode_set(EntryMode::ENTRY_RIGHT).is_err());
+}
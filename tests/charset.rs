@@ -0,0 +1,74 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{CharRom, Charset, EmulatorDriver, Fallback, HdError, HD44780};
+
+#[test]
+fn a00_should_map_katakana_symbols_and_hiragana() {
+    let charset = Charset::new(CharRom::A00, Fallback::Error);
+    assert_eq!(charset.encode("25°C").unwrap(), b"25\xDFC");
+    assert_eq!(charset.encode("ｶﾞ").unwrap(), [0xB6, 0xDE]);
+    assert_eq!(charset.encode("ガ").unwrap(), [0xB6, 0xDE]);
+    assert_eq!(charset.encode("がぱ").unwrap(), [0xB6, 0xDE, 0xCA, 0xDF]);
+    assert_eq!(charset.encode("¥→ü").unwrap(), [0x5C, 0x7E, 0xF5]);
+}
+
+#[test]
+fn a02_should_map_latin_1() {
+    let charset = Charset::new(CharRom::A02, Fallback::Error);
+    assert_eq!(charset.encode("Müller").unwrap(), b"M\xFCller");
+    assert_eq!(charset.encode("\\~Ａ").unwrap(), b"\\~A");
+}
+
+#[test]
+fn a02_should_map_symbols_cyrillic_and_greek() {
+    let charset = Charset::new(CharRom::A02, Fallback::Error);
+    let text = "▶◀“”⏫⏬●↲↑↓→←≤≥▲▼БДЖЗИЙЛПУЦЧШЩЪЫЭα♪Γπ\
+        Σσ♬τ🔔ΘΩδ∞♥ε∩";
+    let codes: Vec<u8> = (0x10..=0x1F).chain(0x80..=0x9F).collect();
+    assert_eq!(charset.encode(text).unwrap(), codes);
+    assert_eq!(
+        charset.encode("►◄⏎ГΩ").unwrap(),
+        [0x10, 0x11, 0x17, 0x92, 0x9A]
+    );
+}
+
+#[test]
+fn fallback_policy_should_be_used_for_unmappable_characters() {
+    let transliterate = Charset::new(CharRom::A00, Fallback::Transliterate);
+    assert_eq!(transliterate.encode("Łódź €").unwrap(), b"Lodz EUR");
+    assert_eq!(transliterate.encode("ß~").unwrap(), b"ss?");
+    let replace = Charset::new(CharRom::A02, Fallback::Replace(0xFF));
+    assert_eq!(replace.encode("aガ").unwrap(), [b'a', 0xFF]);
+    let error = Charset::new(CharRom::A02, Fallback::Error);
+    assert!(matches!(
+        error.encode("ガ"),
+        Err(HdError::UnmappableChar('ガ'))
+    ));
+}
+
+#[test]
+fn write_str_should_write_encoded_text() {
+    let mut lcd = EmulatorDriver::new(8, 1);
+    lcd.write_str("ö°", &Charset::default()).unwrap();
+    assert_eq!([lcd.dd_ram(0), lcd.dd_ram(1)], [0xEF, 0xDF]);
+}
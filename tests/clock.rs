@@ -0,0 +1,52 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use embedded_hal::blocking::delay::DelayUs;
+use hd44780_ntb::{Clock, SystemClock, VirtualClock};
+use std::time::Duration;
+
+#[test]
+fn virtual_clock_should_only_move_by_time_waited() {
+    let mut sut = VirtualClock::new();
+    assert_eq!(sut.now(), Duration::from_micros(0));
+    sut.wait_us(37);
+    sut.delay_us(1520u16);
+    sut.delay_us(100_000u32);
+    sut.advance(Duration::from_millis(1));
+    assert_eq!(sut.now(), Duration::from_micros(101_557 + 1000));
+}
+
+#[test]
+fn virtual_clock_clones_should_share_time() {
+    let sut = VirtualClock::new();
+    let mut delay = sut.clone();
+    delay.delay_us(41u16);
+    assert_eq!(sut.now(), Duration::from_micros(41));
+}
+
+#[test]
+fn system_clock_should_wait_at_least_time_asked_for() {
+    let mut sut = SystemClock::new();
+    let start = sut.now();
+    sut.wait_us(2000);
+    assert!(sut.now() - start >= Duration::from_micros(2000));
+}
@@ -0,0 +1,39 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Fixtures shared by the integration tests.
+
+// Each test crate only uses some of the fixtures.
+#![allow(dead_code)]
+
+use hd44780_ntb::{DisplayMode, EmulatorDriver, FunctionMode, Geometry, Lcd, HD44780};
+
+/// Wrap a driver and initialize it with the display on.
+pub fn lcd<D: HD44780>(driver: D, geometry: Geometry, function: FunctionMode) -> Lcd<D> {
+    let mut lcd = Lcd::new(driver, geometry);
+    lcd.init(function, DisplayMode::DISPLAY_ON, None).unwrap();
+    lcd
+}
+
+/// An initialized emulated display.
+pub fn emulator(geometry: Geometry, function: FunctionMode) -> Lcd<EmulatorDriver> {
+    lcd(EmulatorDriver::with_geometry(geometry), geometry, function)
+}
@@ -0,0 +1,167 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use embedded_hal::digital::v2::OutputPin;
+use hd44780_ntb::{
+    Controller, DisplayMode, DualGpioDriver, FunctionMode, Geometry, Lcd, VirtualClock, HD44780,
+};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// Shared state of the fake display pins.
+#[derive(Default)]
+struct Bus {
+    rs: bool,
+    data: u8,
+    enable: [bool; 2],
+    /// Enable pin, register select, and data bus value for each strobe.
+    strobes: Vec<(usize, bool, u8)>,
+}
+
+enum Role {
+    Rs,
+    Enable(usize),
+    Data(u8),
+}
+
+struct MockPin {
+    bus: Rc<RefCell<Bus>>,
+    role: Role,
+}
+
+impl MockPin {
+    fn set(&mut self, high: bool) {
+        let mut bus = self.bus.borrow_mut();
+        match self.role {
+            Role::Rs => bus.rs = high,
+            Role::Data(bit) => {
+                bus.data = (bus.data & !(1 << bit)) | ((high as u8) << bit);
+            }
+            Role::Enable(e) => {
+                // Display reads the bus on the falling edge.
+                if bus.enable[e] && !high {
+                    let strobe = (e, bus.rs, bus.data);
+                    bus.strobes.push(strobe);
+                }
+                bus.enable[e] = high;
+            }
+        }
+    }
+}
+
+impl OutputPin for MockPin {
+    type Error = ();
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set(false);
+        Ok(())
+    }
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set(true);
+        Ok(())
+    }
+}
+
+fn driver(bus: &Rc<RefCell<Bus>>) -> DualGpioDriver<MockPin, MockPin, MockPin, VirtualClock> {
+    let pin = |role| MockPin {
+        bus: bus.clone(),
+        role,
+    };
+    let data = (0..8).map(|bit| pin(Role::Data(bit))).collect();
+    DualGpioDriver::new(
+        pin(Role::Rs),
+        pin(Role::Enable(0)),
+        pin(Role::Enable(1)),
+        data,
+        VirtualClock::new(),
+    )
+}
+
+#[test]
+fn clear_display_should_go_to_both_controllers() {
+    let bus = Rc::new(RefCell::new(Bus::default()));
+    let mut sut = driver(&bus);
+    sut.clear_display().unwrap();
+    assert_eq!(
+        bus.borrow().strobes,
+        vec![(0, false, 0x01), (1, false, 0x01)]
+    );
+}
+
+#[test]
+fn data_should_go_to_controller_owning_row() {
+    let bus = Rc::new(RefCell::new(Bus::default()));
+    let mut sut = driver(&bus);
+    sut.set_cursor(5, 3).unwrap();
+    bus.borrow_mut().strobes.clear();
+    sut.write_all(b"Hi").unwrap();
+    assert_eq!(sut.active(), Controller::Bottom);
+    assert_eq!(bus.borrow().strobes, vec![(1, true, b'H'), (1, true, b'i')]);
+    assert!(sut.set_cursor(40, 0).is_err());
+}
+
+#[test]
+fn cursor_should_only_show_on_active_controller() {
+    let bus = Rc::new(RefCell::new(Bus::default()));
+    let mut sut = driver(&bus);
+    sut.display_control(DisplayMode::DISPLAY_ON | DisplayMode::CURSOR_ON)
+        .unwrap();
+    sut.set_active(Controller::Bottom).unwrap();
+    assert_eq!(
+        bus.borrow().strobes,
+        vec![
+            (0, false, 0x0E),
+            (1, false, 0x0C),
+            (0, false, 0x0C),
+            (1, false, 0x0E)
+        ]
+    );
+}
+
+#[test]
+fn lcd_with_40x4_geometry_should_reach_bottom_controller() {
+    let bus = Rc::new(RefCell::new(Bus::default()));
+    let mut lcd = Lcd::new(driver(&bus), Geometry::LCD_40X4);
+    lcd.init(
+        FunctionMode::BITS_8 | FunctionMode::LINES_2,
+        DisplayMode::DISPLAY_ON,
+        None,
+    )
+    .unwrap();
+    lcd.set_cursor(5, 3).unwrap();
+    bus.borrow_mut().strobes.clear();
+    lcd.write_all(b"Hi").unwrap();
+    lcd.set_cursor(5, 1).unwrap();
+    lcd.write_all(b"Lo").unwrap();
+    assert_eq!(
+        bus.borrow().strobes[..2],
+        [(1, true, b'H'), (1, true, b'i')]
+    );
+    assert_eq!(
+        bus.borrow().strobes[4..],
+        [(0, false, 0xC5), (0, true, b'L'), (0, true, b'o')]
+    );
+    let glyphs = lcd.state().glyphs();
+    assert_eq!(glyphs[3][5..7], *b"Hi");
+    assert_eq!(glyphs[1][5..7], *b"Lo");
+    assert!(lcd.set_cursor(0, 4).is_err());
+}
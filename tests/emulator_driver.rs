@@ -0,0 +1,105 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{
+    DisplayMode, EmulatorDriver, EntryMode, FunctionMode, Geometry, ReadableHD44780, ShiftMode,
+    HD44780,
+};
+use std::io::Write;
+
+fn display(columns: u8, rows: u8) -> EmulatorDriver {
+    let mut sut = EmulatorDriver::new(columns, rows);
+    sut.init(FunctionMode::LINES_2, DisplayMode::DISPLAY_ON, None)
+        .unwrap();
+    sut
+}
+
+#[test]
+fn should_show_second_line_from_0x40() {
+    let mut sut = display(8, 2);
+    write!(sut, "Hi").unwrap();
+    sut.set_dd_ram_addr(0x40).unwrap();
+    write!(sut, "there").unwrap();
+    assert_eq!(sut.text(), "Hi      \nthere   ");
+    assert_eq!(sut.cursor(), Some((5, 1)));
+}
+
+#[test]
+fn should_wrap_first_line_into_second_and_rows_3_and_4_continue() {
+    let mut sut = display(20, 4);
+    sut.set_dd_ram_addr(0x27).unwrap();
+    write!(sut, "ab").unwrap();
+    assert_eq!(sut.address_counter(), 0x41);
+    assert_eq!(sut.dd_ram(0x40), b'b');
+    sut.set_dd_ram_addr(0x14).unwrap();
+    write!(sut, "row3").unwrap();
+    assert_eq!(sut.glyphs()[2][..4], *b"row3");
+}
+
+#[test]
+fn should_keep_address_counter_in_dd_ram_past_end_of_line() {
+    let mut sut = display(20, 4);
+    sut.set_dd_ram_addr(0x7F).unwrap();
+    write!(sut, "ab").unwrap();
+    assert_eq!(sut.dd_ram(0x7F), b'a');
+    assert_eq!(sut.dd_ram(0x00), b'b');
+    assert_eq!(sut.address_counter(), 0x01);
+    sut.function_set(FunctionMode::LINES_1).unwrap();
+    sut.set_dd_ram_addr(0x7F).unwrap();
+    assert_eq!(sut.read_data(2).unwrap(), vec![b'a', b'b']);
+}
+
+#[test]
+fn should_shift_display_and_entry_shift() {
+    let mut sut = display(4, 1);
+    write!(sut, "abcdef").unwrap();
+    sut.cursor_shift(ShiftMode::DISPLAY_MOVE | ShiftMode::MOVE_LEFT)
+        .unwrap();
+    assert_eq!(sut.text(), "bcde");
+    sut.return_home().unwrap();
+    sut.entry_mode_set(EntryMode::ENTRY_RIGHT).unwrap();
+    sut.set_dd_ram_addr(0x03).unwrap();
+    write!(sut, "xy").unwrap();
+    assert_eq!(sut.text(), "abyx");
+    sut.entry_mode_set(EntryMode::ENTRY_LEFT | EntryMode::ENTRY_SHIFT_DISPLAY)
+        .unwrap();
+    sut.set_dd_ram_addr(0x04).unwrap();
+    write!(sut, "z").unwrap();
+    assert_eq!(sut.text(), "byxz");
+}
+
+#[test]
+fn should_store_cg_ram_and_read_back() {
+    let mut sut = display(16, 2);
+    sut.set_cg_ram_addr(8).unwrap();
+    sut.write_all(&[0x1F; 8]).unwrap();
+    assert_eq!(sut.custom_char(0x09), [0x1F; 8]);
+    sut.set_dd_ram_addr(0).unwrap();
+    sut.write_all(&[0x01, b'A']).unwrap();
+    assert_eq!(sut.glyphs()[0][..2], [0x01, b'A']);
+    assert_eq!(
+        sut.capture_screen(&Geometry::new(2, 1)).unwrap(),
+        vec![vec![0x01, b'A']]
+    );
+    sut.display_control(DisplayMode::DISPLAY_OFF).unwrap();
+    assert_eq!(sut.glyphs()[0][..2], *b"  ");
+}
@@ -0,0 +1,76 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{DisplayMode, EmulatorDriver, Geometry, HdError, ReadableHD44780, HD44780};
+use std::io::Write;
+
+#[test]
+fn address_should_use_preset_row_offsets() {
+    assert_eq!(Geometry::LCD_16X2.address(3, 1).unwrap(), 0x43);
+    assert_eq!(Geometry::LCD_20X4.address(0, 2).unwrap(), 0x14);
+    assert_eq!(Geometry::LCD_20X4.address(19, 3).unwrap(), 0x67);
+    assert_eq!(Geometry::LCD_16X4.address(0, 3).unwrap(), 0x50);
+    assert_eq!(Geometry::LCD_40X2.address(39, 0).unwrap(), 0x27);
+    assert_eq!(Geometry::LCD_16X1_TYPE1.address(7, 0).unwrap(), 0x07);
+    assert_eq!(Geometry::LCD_16X1_TYPE1.address(8, 0).unwrap(), 0x40);
+    assert_eq!(Geometry::LCD_20X4.position(0x55), Some((1, 3)));
+}
+
+#[test]
+fn set_cursor_should_reject_off_screen_positions() {
+    let mut lcd = EmulatorDriver::new(16, 2);
+    assert!(matches!(
+        lcd.set_cursor(&Geometry::LCD_16X2, 16, 0),
+        Err(HdError::OffScreen(16, 0))
+    ));
+    assert!(matches!(
+        Geometry::LCD_8X1.address(0, 1),
+        Err(HdError::OffScreen(0, 1))
+    ));
+}
+
+#[test]
+fn set_cursor_should_move_to_position_on_split_display() {
+    let geometry = Geometry::LCD_16X1_TYPE1;
+    let mut lcd = EmulatorDriver::with_geometry(geometry);
+    lcd.init(geometry.function_mode(), DisplayMode::DISPLAY_ON, None)
+        .unwrap();
+    lcd.set_cursor(&geometry, 6, 0).unwrap();
+    write!(lcd, "ab").unwrap();
+    lcd.set_cursor(&geometry, 8, 0).unwrap();
+    write!(lcd, "cd").unwrap();
+    assert_eq!(lcd.text(), "      abcd      ");
+    assert_eq!(lcd.capture_screen(&geometry).unwrap()[0][6..10], *b"abcd");
+}
+
+#[test]
+fn address_should_not_overflow_for_wide_custom_geometry() {
+    let geometry = Geometry {
+        columns: 255,
+        rows: 1,
+        row_offsets: [0x00; 4],
+        split_column: Some(8),
+    };
+    // 192 columns past the split wraps around from 0x40 to 0x00.
+    assert_eq!(geometry.address(200, 0).unwrap(), 0x00);
+    assert!(geometry.address(255, 0).is_err());
+}
@@ -0,0 +1,154 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::digital::v2::{InputPin, OutputPin};
+use hd44780_ntb::{BidirectionalPin, GpioDriver, HdError, ReadableHD44780, Result, HD44780};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Shared state of the fake display pins.
+#[derive(Default)]
+struct Bus {
+    /// Number of busy flag reads that will still return busy.
+    busy_reads: Cell<u32>,
+    /// Total time(µs) spent in delays.
+    delayed: Cell<u32>,
+    /// Level of each data pin when being driven by the display.
+    levels: RefCell<[bool; 8]>,
+    /// If only `D4`-`D7` are connected so bytes are read a nibble at a time.
+    four_bit: Cell<bool>,
+    /// Number of times enable has been set high.
+    strobes: Cell<u32>,
+}
+
+struct MockPin {
+    bus: Rc<Bus>,
+    bit: usize,
+}
+
+impl OutputPin for MockPin {
+    type Error = ();
+    fn set_low(&mut self) -> std::result::Result<(), Self::Error> {
+        Ok(())
+    }
+    fn set_high(&mut self) -> std::result::Result<(), Self::Error> {
+        if self.bit == 10 {
+            self.bus.strobes.set(self.bus.strobes.get() + 1);
+        }
+        Ok(())
+    }
+}
+
+impl InputPin for MockPin {
+    type Error = ();
+    fn is_high(&self) -> std::result::Result<bool, Self::Error> {
+        // A 4 bit bus gets the high nibble on the first strobe of each pair.
+        let low_nibble = self.bus.four_bit.get() && self.bus.strobes.get() & 1 == 0;
+        let bit = if low_nibble { self.bit - 4 } else { self.bit };
+        if bit == 7 {
+            let busy = self.bus.busy_reads.get();
+            self.bus.busy_reads.set(busy.saturating_sub(1));
+            return Ok(busy > 0);
+        }
+        Ok(self.bus.levels.borrow()[bit])
+    }
+    fn is_low(&self) -> std::result::Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+impl BidirectionalPin for MockPin {
+    fn set_input(&mut self) -> Result {
+        Ok(())
+    }
+    fn set_output(&mut self) -> Result {
+        Ok(())
+    }
+}
+
+struct MockDelay(Rc<Bus>);
+
+impl DelayUs<u16> for MockDelay {
+    fn delay_us(&mut self, us: u16) {
+        self.0.delayed.set(self.0.delayed.get() + us as u32);
+    }
+}
+
+fn driver(bus: &Rc<Bus>) -> GpioDriver<MockPin, MockPin, MockPin, MockDelay, MockPin> {
+    let pin = |bit| MockPin {
+        bus: bus.clone(),
+        bit,
+    };
+    let data = if bus.four_bit.get() { 4..8 } else { 0..8 }
+        .map(pin)
+        .collect();
+    GpioDriver::with_rw(pin(8), pin(9), pin(10), data, MockDelay(bus.clone()))
+}
+
+#[test]
+fn clear_display_should_return_once_busy_flag_clears() {
+    let bus = Rc::new(Bus::default());
+    let mut sut = driver(&bus);
+    bus.busy_reads.set(3);
+    sut.clear_display().unwrap();
+    assert_eq!(bus.busy_reads.get(), 0);
+    // Far less than the 42 times command delay used without the busy flag.
+    assert!(bus.delayed.get() < 200);
+}
+
+#[test]
+fn command_should_time_out_when_display_stays_busy() {
+    let bus = Rc::new(Bus::default());
+    let mut sut = driver(&bus);
+    sut.set_busy_timeout(100);
+    bus.busy_reads.set(u32::MAX);
+    match sut.return_home() {
+        Err(HdError::BusyTimeout(100)) => {}
+        other => panic!("Expected busy timeout but got {:?}", other),
+    }
+}
+
+#[test]
+fn read_address_counter_should_mask_busy_flag() {
+    let bus = Rc::new(Bus::default());
+    let mut sut = driver(&bus);
+    *bus.levels.borrow_mut() = [true, false, true, false, false, false, true, true];
+    assert_eq!(sut.read_address_counter().unwrap(), 0x45);
+    assert_eq!(sut.read_data(3).unwrap(), vec![0x45; 3]);
+}
+
+#[test]
+fn read_should_combine_nibbles_on_4_bit_bus() {
+    let bus = Rc::new(Bus::default());
+    bus.four_bit.set(true);
+    let mut sut = driver(&bus);
+    bus.busy_reads.set(2);
+    // 0x5A so swapped nibbles would show.
+    *bus.levels.borrow_mut() = [false, true, false, true, true, false, true, false];
+    assert_eq!(sut.read_address_counter().unwrap(), 0x5A);
+    assert_eq!(bus.busy_reads.get(), 0);
+    assert_eq!(sut.read_data(2).unwrap(), vec![0x5A; 2]);
+    // Two strobes for each of 3 busy flag polls and the address counter
+    // read then 2 data reads each followed by a poll.
+    assert_eq!(bus.strobes.get(), 16);
+}
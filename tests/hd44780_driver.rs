@@ -20,7 +20,12 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use hd44780_ntb::{FunctionMode, SpyDriver, HD44780};
+use hd44780_ntb::{
+    Clock, DisplayMode, Event, FunctionMode, Geometry, HdError, Instruction, SpyDriver,
+    VirtualClock, HD44780,
+};
+use std::io::Write;
+use std::time::Duration;
 
 #[test]
 fn function_set_should_return_error_when_2_line_and_5_x_10_font() {
@@ -28,3 +33,105 @@ fn function_set_should_return_error_when_2_line_and_5_x_10_font() {
     let fm = FunctionMode::LINES_2 | FunctionMode::DOTS_5X10;
     assert!(sut.function_set(fm).is_err());
 }
+
+#[test]
+fn spy_driver_should_log_commands_and_writes_in_order() {
+    let mut sut = SpyDriver::new();
+    sut.set_dd_ram_addr(0x40).unwrap();
+    sut.write_all(b"Hi").unwrap();
+    sut.clear_display().unwrap();
+    let events = sut.events();
+    assert_eq!(events.len(), 3);
+    assert_eq!(
+        events[0].instruction(),
+        Some(Instruction::SetDdRamAddr(0x40))
+    );
+    assert_eq!(events[0].command().map(|c| c.byte()), Some(0xC0));
+    assert_eq!(events[1].data(), Some(&b"Hi"[..]));
+    assert_eq!(events[1].instruction(), None);
+    assert_eq!(events[2].instruction(), Some(Instruction::ClearDisplay));
+    assert!(events[0].when() <= events[2].when());
+    assert_eq!(sut.written(), b"Hi");
+}
+
+#[test]
+fn init_should_send_8_bit_sequence_with_delays() {
+    let mut sut = SpyDriver::new();
+    sut.init(
+        FunctionMode::BITS_8 | FunctionMode::LINES_2,
+        DisplayMode::DISPLAY_ON,
+        None,
+    )
+    .unwrap();
+    let events = sut.events();
+    assert!(matches!(events[0], Event::Init(_)));
+    let sent: Vec<(u8, u16)> = sut.commands().map(|c| (c.byte(), c.delay())).collect();
+    assert_eq!(
+        sent,
+        [
+            (0x33, 3700),
+            (0x33, 111),
+            (0x38, 37),
+            (0x0C, 37),
+            (0x06, 37),
+            (0x01, 1554)
+        ]
+    );
+    assert_eq!(events[6].instruction(), Some(Instruction::ClearDisplay));
+}
+
+#[test]
+fn init_should_send_4_bit_sequence_or_fail_for_8_bit_mode() {
+    let mut sut = SpyDriver::with_bus_len(4);
+    sut.init(FunctionMode::LINES_2, None, None).unwrap();
+    let sent: Vec<u8> = sut.commands().map(|c| c.byte()).collect();
+    assert_eq!(sent, [0x33, 0x32, 0x28, 0x08, 0x06, 0x01]);
+    sut.clear();
+    assert!(matches!(
+        sut.init(FunctionMode::BITS_8, None, None),
+        Err(HdError::InvalidDataBusLen)
+    ));
+    assert_eq!(sut.events().len(), 2);
+    sut.command(0x00, 7).unwrap();
+    assert_eq!(sut.events()[2].delay(), 7);
+    assert_eq!(sut.events()[2].instruction(), None);
+    assert!(SpyDriver::with_bus_len(6).init(None, None, None).is_err());
+}
+
+#[test]
+fn clear_display_should_be_followed_by_its_delay_with_virtual_clock() {
+    let clock = VirtualClock::new();
+    let mut sut = SpyDriver::with_clock(clock.clone(), 8);
+    sut.clear_display().unwrap();
+    sut.write_all(b"A").unwrap();
+    sut.return_home().unwrap();
+    let events = sut.events();
+    assert_eq!(events[0].when(), Duration::from_micros(0));
+    assert_eq!(events[1].when(), Duration::from_micros(1554));
+    assert_eq!(
+        events[2].when() - events[1].when(),
+        Duration::from_micros(0)
+    );
+    assert_eq!(clock.now(), Duration::from_micros(1554 * 2));
+    assert_eq!(sut.clock().now(), clock.now());
+}
+
+#[test]
+fn new_should_use_virtual_clock_that_does_not_sleep() {
+    let mut sut = SpyDriver::new();
+    sut.init(None, None, None).unwrap();
+    let start = sut.clock().now();
+    sut.clear_display().unwrap();
+    assert_eq!(sut.clock().now() - start, Duration::from_micros(1554));
+}
+
+#[test]
+fn set_cursor_should_return_error_for_second_controller_rows() {
+    let mut sut = SpyDriver::new();
+    sut.set_cursor(&Geometry::LCD_40X4, 5, 1).unwrap();
+    assert!(matches!(
+        sut.set_cursor(&Geometry::LCD_40X4, 5, 2),
+        Err(HdError::InvalidDdRamAddr(0x85))
+    ));
+    assert_eq!(sut.instructions(), [Instruction::SetDdRamAddr(0x45)]);
+}
@@ -0,0 +1,132 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use embedded_hal::blocking::i2c;
+use hd44780_ntb::{
+    Buttons, FunctionMode, HdError, I2cMcp230xxDriver, Mcp230xx, PinMap, VirtualClock, HD44780,
+    MCP230XX_ADDRESS,
+};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// Bytes of each I²C write.
+type WriteLog = Rc<RefCell<Vec<Vec<u8>>>>;
+
+/// Records every I²C write and answers reads with a fixed port value.
+#[derive(Clone, Default)]
+struct MockI2c {
+    writes: WriteLog,
+    port: [u8; 2],
+}
+
+impl i2c::Write for MockI2c {
+    type Error = ();
+    fn write(&mut self, _address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.writes.borrow_mut().push(Vec::from(bytes));
+        Ok(())
+    }
+}
+
+impl i2c::WriteRead for MockI2c {
+    type Error = ();
+    fn write_read(
+        &mut self,
+        _address: u8,
+        _bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        buffer.copy_from_slice(&self.port[..buffer.len()]);
+        Ok(())
+    }
+}
+
+#[test]
+fn init_should_set_up_plate_expander_before_wake_up_sequence() {
+    let bus = MockI2c::default();
+    let mut sut = I2cMcp230xxDriver::rgb_plate(bus.clone(), MCP230XX_ADDRESS, VirtualClock::new());
+    sut.init(FunctionMode::LINES_2, None, None).unwrap();
+    let writes = bus.writes.borrow();
+    // IOCON byte mode, IODIR with buttons as inputs, pull-ups on buttons.
+    assert_eq!(writes[0], vec![0x0A, 0x20, 0x20]);
+    assert_eq!(writes[1], vec![0x00, 0x3F, 0x00]);
+    assert_eq!(writes[2], vec![0x0C, 0x1F, 0x00]);
+    // 0x33 wake-up: D4, D5 are GPB4, GPB3 and E is GPB5 with RS low.
+    assert_eq!(
+        writes[4],
+        vec![0x14, 0x00, 0x38, 0x00, 0x18, 0x00, 0x38, 0x00, 0x18]
+    );
+}
+
+#[test]
+fn set_rgb_should_drive_active_low_leds() {
+    let bus = MockI2c::default();
+    let mut sut = I2cMcp230xxDriver::rgb_plate(bus.clone(), MCP230XX_ADDRESS, VirtualClock::new());
+    sut.set_rgb(false, true, false).unwrap();
+    sut.write_all(&[0x00]).unwrap();
+    let writes = bus.writes.borrow();
+    // Red (GPA6) and blue (GPB0) off, RS (GPB7) high for data.
+    assert_eq!(writes[0], vec![0x14, 0x40, 0x81]);
+    assert_eq!(writes[1][1..3], [0x40, 0xA1]);
+}
+
+#[test]
+fn buttons_should_report_pressed_buttons() {
+    let bus = MockI2c {
+        port: [0b1111_0110, 0xFF],
+        ..MockI2c::default()
+    };
+    let mut sut = I2cMcp230xxDriver::rgb_plate(bus, MCP230XX_ADDRESS, VirtualClock::new());
+    assert_eq!(sut.buttons().unwrap(), Buttons::SELECT | Buttons::UP);
+}
+
+#[test]
+fn backpack_should_not_support_rgb_or_buttons() {
+    let mut sut =
+        I2cMcp230xxDriver::backpack(MockI2c::default(), MCP230XX_ADDRESS, VirtualClock::new());
+    assert!(sut.set_rgb(true, false, false).is_err());
+    assert!(sut.buttons().is_err());
+}
+
+#[test]
+fn new_should_reject_port_b_bits_on_mcp23008() {
+    let result = I2cMcp230xxDriver::new(
+        MockI2c::default(),
+        MCP230XX_ADDRESS,
+        Mcp230xx::Mcp23008,
+        PinMap::RGB_PLATE,
+        VirtualClock::new(),
+    );
+    assert!(matches!(result, Err(HdError::InvalidPinMap(15))));
+    let pins = PinMap {
+        backlight: Some(16),
+        ..PinMap::RGB_PLATE
+    };
+    let result = I2cMcp230xxDriver::new(
+        MockI2c::default(),
+        0x20,
+        Mcp230xx::Mcp23017,
+        pins,
+        VirtualClock::new(),
+    );
+    assert!(matches!(result, Err(HdError::InvalidPinMap(16))));
+}
@@ -0,0 +1,101 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use embedded_hal::blocking::i2c;
+use hd44780_ntb::{
+    FunctionMode, HdError, I2cPcf8574Driver, PinMap, VirtualClock, HD44780, PCF8574_ADDRESS,
+};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// Address and bytes of each I²C write.
+type WriteLog = Rc<RefCell<Vec<(u8, Vec<u8>)>>>;
+
+/// Records every I²C write so they can be checked after the driver is done.
+#[derive(Clone, Default)]
+struct MockI2c(WriteLog);
+
+impl MockI2c {
+    fn bytes(&self) -> Vec<u8> {
+        self.0
+            .borrow()
+            .iter()
+            .flat_map(|(_, b)| b.clone())
+            .collect()
+    }
+}
+
+impl i2c::Write for MockI2c {
+    type Error = ();
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.0.borrow_mut().push((address, Vec::from(bytes)));
+        Ok(())
+    }
+}
+
+#[test]
+fn init_should_send_wake_up_nibbles_with_enable_strobes() {
+    let bus = MockI2c::default();
+    let mut sut = I2cPcf8574Driver::new(bus.clone(), PCF8574_ADDRESS, VirtualClock::new());
+    sut.init(FunctionMode::LINES_2, None, None).unwrap();
+    assert!(bus.0.borrow().iter().all(|(a, _)| *a == PCF8574_ADDRESS));
+    let bytes = bus.bytes();
+    // Backlight only, then 0x33 and 0x32 as nibbles with enable high then low.
+    assert_eq!(
+        &bytes[..9],
+        &[0x08, 0x3C, 0x38, 0x3C, 0x38, 0x3C, 0x38, 0x2C, 0x28]
+    );
+}
+
+#[test]
+fn init_should_return_error_when_8_bit_mode_requested() {
+    let mut sut = I2cPcf8574Driver::new(MockI2c::default(), PCF8574_ADDRESS, VirtualClock::new());
+    assert!(sut.init(FunctionMode::BITS_8, None, None).is_err());
+}
+
+#[test]
+fn write_should_use_pin_map_and_backlight_state() {
+    let bus = MockI2c::default();
+    let mut sut = I2cPcf8574Driver::with_pins(
+        bus.clone(),
+        0x20,
+        PinMap::PCF8574_MJKDZ,
+        VirtualClock::new(),
+    )
+    .unwrap();
+    sut.set_backlight(false).unwrap();
+    sut.write_all(b"A").unwrap();
+    // Backlight is active low on bit 7 and RS is bit 6, 'A' = 0x41.
+    assert_eq!(bus.bytes(), vec![0x80, 0xD4, 0xC4, 0xD1, 0xC1]);
+    assert!(!sut.backlight());
+}
+
+#[test]
+fn with_pins_should_reject_bits_the_expander_does_not_have() {
+    let pins = PinMap {
+        e: 8,
+        ..PinMap::PCF8574
+    };
+    let result = I2cPcf8574Driver::with_pins(MockI2c::default(), 0x20, pins, VirtualClock::new());
+    assert!(matches!(result, Err(HdError::InvalidPinMap(8))));
+}
@@ -0,0 +1,101 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{DisplayMode, EntryMode, FunctionMode, HdError, Instruction, ShiftMode};
+use std::convert::TryFrom;
+
+#[test]
+fn try_from_should_decode_by_highest_set_bit() {
+    assert_eq!(
+        Instruction::try_from(0x01).unwrap(),
+        Instruction::ClearDisplay
+    );
+    assert_eq!(
+        Instruction::try_from(0x03).unwrap(),
+        Instruction::ReturnHome
+    );
+    assert_eq!(
+        Instruction::try_from(0x06).unwrap(),
+        Instruction::EntryModeSet(EntryMode::default())
+    );
+    assert_eq!(
+        Instruction::try_from(0x0E).unwrap(),
+        Instruction::DisplayControl(DisplayMode::DISPLAY_ON | DisplayMode::CURSOR_ON)
+    );
+    assert_eq!(
+        Instruction::try_from(0x18).unwrap(),
+        Instruction::CursorShift(ShiftMode::DISPLAY_MOVE | ShiftMode::MOVE_LEFT)
+    );
+    assert_eq!(
+        Instruction::try_from(0x28).unwrap(),
+        Instruction::FunctionSet(FunctionMode::LINES_2)
+    );
+    assert_eq!(
+        Instruction::try_from(0x48).unwrap(),
+        Instruction::SetCgRamAddr(0x08)
+    );
+    assert_eq!(
+        Instruction::try_from(0xC0).unwrap(),
+        Instruction::SetDdRamAddr(0x40)
+    );
+    assert!(matches!(
+        Instruction::try_from(0x00),
+        Err(HdError::UnknownCommand(0))
+    ));
+}
+
+#[test]
+fn encode_should_round_trip_every_command_byte() {
+    const HOME: u8 = Instruction::ReturnHome.encode();
+    assert_eq!(HOME, 0x02);
+    for byte in 0x01..=0xFF {
+        let instruction = Instruction::try_from(byte).unwrap();
+        assert_eq!(
+            Instruction::try_from(instruction.encode()).unwrap(),
+            instruction
+        );
+        assert_eq!(u8::from(instruction), instruction.encode());
+    }
+    assert_eq!(Instruction::SetCgRamAddr(0xFF).encode(), 0x7F);
+    assert_eq!(Instruction::ClearDisplay.delay(37), 1554);
+    assert_eq!(Instruction::SetDdRamAddr(0).delay(37), 37);
+}
+
+#[test]
+fn display_should_describe_instruction() {
+    let described: Vec<String> = [0x01, 0x07, 0x0C, 0x14, 0x38, 0x40, 0xC5]
+        .iter()
+        .map(|&byte| Instruction::try_from(byte).unwrap().to_string())
+        .collect();
+    assert_eq!(
+        described,
+        [
+            "Clear display",
+            "Entry mode set: increment, shift display",
+            "Display control: display on, cursor off, blink off",
+            "Cursor shift: move cursor right",
+            "Function set: 8 bit, 2 line, 5x8 dots",
+            "Set CG RAM address 0x00",
+            "Set DD RAM address 0x45",
+        ]
+    );
+}
@@ -0,0 +1,101 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use hd44780_ntb::{CustomChar, EntryMode, FunctionMode, Geometry, HdError, HD44780};
+use std::io::Write;
+
+const HEART: &str = "
+    .....
+    .#.#.
+    #####
+    #####
+    .###.
+    ..#..
+    .....
+";
+
+#[test]
+fn from_art_should_build_rows() {
+    let heart: CustomChar = HEART.parse().unwrap();
+    assert_eq!(
+        heart.rows(),
+        [0x00, 0x0A, 0x1F, 0x1F, 0x0E, 0x04, 0x00, 0x00]
+    );
+    assert!(!heart.is_5x10());
+    assert!(matches!(
+        CustomChar::from_art("#.#\n#x#"),
+        Err(HdError::InvalidCustomChar(_))
+    ));
+}
+
+#[test]
+fn load_custom_char_should_upload_and_restore_address() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    sut.set_cursor(3, 1).unwrap();
+    sut.load_custom_char(2, &CustomChar::from_art(HEART).unwrap())
+        .unwrap();
+    sut.write_all(&[0x02]).unwrap();
+    let display = sut.get_ref();
+    assert_eq!(display.custom_char(2)[1..3], [0x0A, 0x1F]);
+    assert_eq!(display.dd_ram(0x43), 0x02);
+    assert_eq!(display.address_counter(), 0x44);
+}
+
+#[test]
+fn load_custom_char_should_restore_decrement_entry_mode() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    sut.entry_mode_set(EntryMode::ENTRY_RIGHT).unwrap();
+    sut.load_custom_char(0, &CustomChar::new([0x1F; 8]))
+        .unwrap();
+    assert_eq!(sut.get_ref().custom_char(0), [0x1F; 8]);
+    assert_eq!(sut.get_ref().entry_mode(), EntryMode::ENTRY_RIGHT);
+}
+
+#[test]
+fn load_custom_char_should_check_slot_against_font() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::DOTS_5X10);
+    let tall = CustomChar::new_5x10([0x1F; 11]);
+    assert!(matches!(
+        sut.load_custom_char(4, &tall),
+        Err(HdError::InvalidCgRamSlot(4))
+    ));
+    sut.load_custom_char(3, &tall).unwrap();
+    assert_eq!(sut.get_ref().cg_ram()[48..59], [0x1F; 11]);
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    assert!(sut.load_custom_char(7, &tall).is_err());
+}
+
+#[test]
+fn load_custom_char_should_blank_5x10_rows_below_5x8_character() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::DOTS_5X10);
+    sut.load_custom_char(1, &CustomChar::new_5x10([0x1F; 11]))
+        .unwrap();
+    sut.load_custom_char(1, &HEART.parse().unwrap()).unwrap();
+    let cg_ram = sut.get_ref().cg_ram();
+    assert_eq!(
+        cg_ram[16..24],
+        [0x00, 0x0A, 0x1F, 0x1F, 0x0E, 0x04, 0x00, 0x00]
+    );
+    assert_eq!(cg_ram[24..27], [0x00; 3]);
+}
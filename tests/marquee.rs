@@ -0,0 +1,119 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use hd44780_ntb::{FunctionMode, Geometry, Marquee, ScrollStyle};
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn tick_should_wrap_one_line_with_gap_and_pause() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    sut.write_all(b"Status").unwrap();
+    let mut marquee = Marquee::line(0, 1, 4, "ABCDEF");
+    marquee.set_gap(2);
+    marquee.set_step(ms(100));
+    let start = Instant::now();
+    assert!(marquee.tick(&mut sut, start).unwrap());
+    assert_eq!(sut.get_ref().text(), "Status          \nABCD            ");
+    assert!(!marquee.tick(&mut sut, start + ms(500)).unwrap());
+    let mut seen = vec![];
+    for step in 0..8 {
+        marquee
+            .tick(&mut sut, start + ms(1000 + step * 100))
+            .unwrap();
+        seen.push(sut.get_ref().text()[17..21].to_string());
+    }
+    assert_eq!(
+        seen,
+        ["BCDE", "CDEF", "DEF ", "EF  ", "F  A", "  AB", " ABC", "ABCD"]
+    );
+    // Back at the start so it pauses again.
+    assert_eq!(marquee.next_tick(), Some(start + ms(2700)));
+    assert!(sut.get_ref().text().starts_with("Status"));
+}
+
+#[test]
+fn tick_should_bounce_using_display_shift() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut marquee = Marquee::hardware(0, "Watch me move left!!");
+    marquee.set_style(ScrollStyle::Bounce);
+    marquee.set_pause(ms(0));
+    let start = Instant::now();
+    marquee.tick(&mut sut, start).unwrap();
+    let mut shifts = vec![];
+    for step in 1..=9 {
+        marquee.tick(&mut sut, start + ms(step * 300)).unwrap();
+        shifts.push(sut.get_ref().display_shift());
+    }
+    assert_eq!(shifts, [1, 2, 3, 4, 3, 2, 1, 0, 1]);
+    assert_eq!(marquee.offset(), 1);
+}
+
+#[test]
+fn tick_should_leave_short_text_alone() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut marquee = Marquee::line(2, 0, 8, "Short");
+    let start = Instant::now();
+    assert!(marquee.tick(&mut sut, start).unwrap());
+    assert!(!marquee.tick(&mut sut, start + ms(5000)).unwrap());
+    assert_eq!(&sut.get_ref().text()[..10], "  Short   ");
+    marquee.reset();
+    assert_eq!(marquee.next_tick(), None);
+}
+
+#[test]
+fn hardware_should_keep_long_text_in_its_dd_ram_line() {
+    let mut sut = common::emulator(Geometry::LCD_20X4, FunctionMode::LINES_2);
+    for row in 0..4 {
+        sut.set_cursor(0, row).unwrap();
+        sut.write_all(b"Row").unwrap();
+    }
+    let mut marquee = Marquee::hardware(2, "This text is longer than twenty");
+    marquee.tick(&mut sut, Instant::now()).unwrap();
+    let text = sut.get_ref().text();
+    let rows: Vec<&str> = text.lines().collect();
+    // The line is circular so the end of the text continues on row 0.
+    assert_eq!(rows[0], "than twenty         ");
+    assert_eq!(rows[1], "Row                 ");
+    assert_eq!(rows[2], "This text is longer ");
+    assert_eq!(rows[3], "Row                 ");
+}
+
+#[test]
+fn hardware_should_only_write_its_row_when_text_fits() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    sut.write_all(b"Status").unwrap();
+    sut.set_cursor(0, 1).unwrap();
+    sut.write_all(b"Other").unwrap();
+    let mut marquee = Marquee::hardware(1, "Short");
+    let start = Instant::now();
+    assert!(marquee.tick(&mut sut, start).unwrap());
+    assert!(!marquee.tick(&mut sut, start + ms(5000)).unwrap());
+    assert_eq!(sut.get_ref().text(), "Status          \nShort           ");
+    assert_eq!(sut.get_ref().display_shift(), 0);
+}
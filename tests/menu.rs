@@ -0,0 +1,155 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use hd44780_ntb::{Buttons, FunctionMode, Geometry, Key, Menu, MenuEvent, MenuItem, MenuResponse};
+
+fn menu() -> Menu {
+    Menu::new(vec![
+        MenuItem::submenu(
+            "Display",
+            vec![
+                MenuItem::toggle("Backlight", true),
+                MenuItem::number("Contrast", 5, 0, 6, 2),
+            ],
+        ),
+        MenuItem::toggle("Sound", false),
+        MenuItem::action("Reboot"),
+    ])
+}
+
+#[test]
+fn render_should_show_marker_values_and_scroll_arrows() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut menu = menu();
+    menu.load(&mut sut).unwrap();
+    menu.render(&mut sut).unwrap();
+    let glyphs = sut.get_ref().glyphs();
+    assert_eq!(glyphs[0], b"\x00Display      > ");
+    assert_eq!(glyphs[1], b" Sound      Off\x02");
+    assert_eq!(
+        sut.get_ref().custom_char(0),
+        [0, 0x10, 0x18, 0x1C, 0x18, 0x10, 0, 0]
+    );
+    menu.handle(MenuEvent::Down);
+    menu.handle(MenuEvent::Down);
+    menu.render(&mut sut).unwrap();
+    let glyphs = sut.get_ref().glyphs();
+    assert_eq!(glyphs[0], b" Sound      Off\x01");
+    assert_eq!(glyphs[1], b"\x00Reboot         ");
+    assert_eq!(
+        menu.handle(MenuEvent::Select),
+        Some(MenuResponse::Action("Reboot".into()))
+    );
+}
+
+#[test]
+fn handle_should_enter_submenus_and_edit_values() {
+    let mut sut = common::emulator(Geometry::LCD_20X4, FunctionMode::LINES_2);
+    let mut menu = menu();
+    menu.handle(MenuEvent::Select);
+    assert_eq!(menu.depth(), 1);
+    assert_eq!(
+        menu.handle(MenuEvent::Select),
+        Some(MenuResponse::Toggled("Backlight".into(), false))
+    );
+    menu.handle(MenuEvent::Down);
+    menu.handle(MenuEvent::Select);
+    assert!(menu.is_editing());
+    menu.handle(MenuEvent::Up);
+    menu.render(&mut sut).unwrap();
+    let text = sut.get_ref().text();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], " Backlight      Off ");
+    assert_eq!(lines[1], "?Contrast       <6> ");
+    assert_eq!(lines[2], " ".repeat(20));
+    assert_eq!(
+        menu.handle(MenuEvent::Back),
+        Some(MenuResponse::Changed("Contrast".into(), 6))
+    );
+    assert_eq!(menu.handle(MenuEvent::Back), None);
+    assert_eq!(menu.selected().map(MenuItem::label), Some("Display"));
+    assert_eq!(menu.handle(MenuEvent::Back), Some(MenuResponse::Exit));
+}
+
+#[test]
+fn events_should_map_from_keys_and_buttons() {
+    assert_eq!(MenuEvent::from_key(Key::Enter), Some(MenuEvent::Select));
+    assert_eq!(MenuEvent::from_key(Key::Escape), Some(MenuEvent::Back));
+    assert_eq!(MenuEvent::from_key(Key::Char('x')), None);
+    assert_eq!(
+        MenuEvent::from_buttons(Buttons::LEFT | Buttons::DOWN),
+        Some(MenuEvent::Down)
+    );
+    assert_eq!(
+        MenuEvent::from_buttons(Buttons::RIGHT),
+        Some(MenuEvent::Select)
+    );
+    assert_eq!(MenuEvent::from_buttons(Buttons::empty()), None);
+}
+
+#[test]
+fn render_should_use_5x10_codes_for_custom_characters() {
+    let mut sut = common::emulator(Geometry::LCD_16X1, FunctionMode::DOTS_5X10);
+    assert!(sut.state().is_5x10());
+    let mut menu = menu();
+    menu.set_first_slot(1);
+    menu.load(&mut sut).unwrap();
+    menu.render(&mut sut).unwrap();
+    assert_eq!(sut.get_ref().glyphs()[0], b"\x02Display      >\x06");
+    menu.set_first_slot(2);
+    assert!(menu.load(&mut sut).is_err());
+}
+
+#[test]
+fn number_should_order_limits_and_use_positive_step() {
+    let item = MenuItem::number("Level", 20, 10, 0, -3);
+    assert_eq!(
+        item,
+        MenuItem::Number {
+            label: "Level".into(),
+            value: 10,
+            min: 0,
+            max: 10,
+            step: 3,
+        }
+    );
+    let mut menu = Menu::new(vec![MenuItem::number("Level", 5, 0, 10, 0)]);
+    menu.handle(MenuEvent::Select);
+    menu.handle(MenuEvent::Up);
+    assert_eq!(
+        menu.handle(MenuEvent::Select),
+        Some(MenuResponse::Changed("Level".into(), 6))
+    );
+}
+
+#[test]
+fn render_should_not_overflow_slot_codes() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut menu = menu();
+    menu.set_first_slot(254);
+    menu.render(&mut sut).unwrap();
+    let glyphs = sut.get_ref().glyphs();
+    assert_eq!(glyphs[0][0], 254);
+    assert_eq!(glyphs[1][15], 255);
+}
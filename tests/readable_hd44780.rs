@@ -0,0 +1,123 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{
+    DisplayMode, EmulatorDriver, FunctionMode, HdError, ReadableHD44780, Result, HD44780,
+};
+use std::io::{Result as IOResult, Write};
+
+/// Emulator that can be made to fail reads part way through a dump.
+struct Readable {
+    lcd: EmulatorDriver,
+    fail: bool,
+}
+
+impl Readable {
+    fn new(function: FunctionMode) -> Self {
+        let mut lcd = EmulatorDriver::new(16, 2);
+        lcd.init(function, DisplayMode::DISPLAY_ON, None).unwrap();
+        Readable { lcd, fail: false }
+    }
+}
+
+impl Write for Readable {
+    fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
+        self.lcd.write(buf)
+    }
+    fn flush(&mut self) -> IOResult<()> {
+        self.lcd.flush()
+    }
+}
+
+impl HD44780 for Readable {
+    const COMMAND_DELAY: u16 = 0;
+    fn command(&mut self, byte: u8, delay: u16) -> Result {
+        self.lcd.command(byte, delay)
+    }
+}
+
+impl ReadableHD44780 for Readable {
+    fn read_address_counter(&mut self) -> Result<u8> {
+        self.lcd.read_address_counter()
+    }
+    fn read_data(&mut self, len: usize) -> Result<Vec<u8>> {
+        if self.fail {
+            return Err(HdError::ReadInputPin("D7"));
+        }
+        self.lcd.read_data(len)
+    }
+}
+
+#[test]
+fn dump_dd_ram_should_read_both_lines_and_restore_address() {
+    let mut sut = Readable::new(FunctionMode::LINES_2);
+    sut.set_dd_ram_addr(0x27).unwrap();
+    sut.write_all(b"A").unwrap();
+    sut.set_dd_ram_addr(0x40).unwrap();
+    sut.write_all(b"B").unwrap();
+    sut.set_dd_ram_addr(0x45).unwrap();
+    let dd_ram = sut.dump_dd_ram().unwrap();
+    assert_eq!(dd_ram.len(), 80);
+    // The address counter runs from 0x27 straight on to 0x40.
+    assert_eq!(dd_ram[39..41], *b"AB");
+    assert_eq!(sut.lcd.address_counter(), 0x45);
+    sut.write_all(b"C").unwrap();
+    assert_eq!(sut.lcd.dd_ram(0x45), b'C');
+}
+
+#[test]
+fn dump_cg_ram_should_read_all_characters_and_restore_dd_ram_address() {
+    let mut sut = Readable::new(FunctionMode::LINES_2);
+    sut.set_cg_ram_addr(0x3F).unwrap();
+    sut.write_all(&[0x1F]).unwrap();
+    sut.set_dd_ram_addr(0x03).unwrap();
+    let cg_ram = sut.dump_cg_ram().unwrap();
+    assert_eq!(cg_ram.len(), 64);
+    assert_eq!(cg_ram[63], 0x1F);
+    assert_eq!(cg_ram[..63], [0; 63]);
+    // Writes go back to DD RAM where they left off.
+    sut.write_all(b"D").unwrap();
+    assert_eq!(sut.lcd.dd_ram(0x03), b'D');
+}
+
+#[test]
+fn dumps_should_restore_address_when_read_fails() {
+    let mut sut = Readable::new(FunctionMode::empty());
+    sut.set_dd_ram_addr(0x12).unwrap();
+    sut.fail = true;
+    assert!(matches!(sut.dump_dd_ram(), Err(HdError::ReadInputPin(_))));
+    assert_eq!(sut.lcd.address_counter(), 0x12);
+    assert!(matches!(sut.dump_cg_ram(), Err(HdError::ReadInputPin(_))));
+    assert_eq!(sut.lcd.address_counter(), 0x12);
+    sut.write_all(b"E").unwrap();
+    assert_eq!(sut.lcd.dd_ram(0x12), b'E');
+}
+
+#[test]
+fn dump_dd_ram_should_read_one_line_in_1_line_mode() {
+    let mut sut = Readable::new(FunctionMode::empty());
+    sut.set_dd_ram_addr(0x4F).unwrap();
+    sut.write_all(b"Z").unwrap();
+    let dd_ram = sut.dump_dd_ram().unwrap();
+    assert_eq!(dd_ram.len(), 80);
+    assert_eq!(dd_ram[79], b'Z');
+}
@@ -0,0 +1,114 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{CharRom, DisplayMode, EmulatorDriver, FunctionMode, Renderer, Theme, HD44780};
+use std::io::Write;
+
+fn display(fs: FunctionMode, columns: u8, rows: u8) -> EmulatorDriver {
+    let mut lcd = EmulatorDriver::new(columns, rows);
+    lcd.init(fs, DisplayMode::DISPLAY_ON, None).unwrap();
+    lcd
+}
+
+/// Top left corner of dot `x`, `y` with default 3 pixel dots and 1 pixel gaps.
+fn dot(x: u32, y: u32) -> (u32, u32) {
+    (8 + x * 4, 8 + y * 4)
+}
+
+#[test]
+fn render_should_draw_rom_characters_as_dots() {
+    let mut lcd = display(FunctionMode::LINES_2, 16, 2);
+    write!(lcd, "A").unwrap();
+    let image = Renderer::new(CharRom::A00).render(&lcd);
+    assert_eq!((image.width(), image.height()), (396, 84));
+    // Top row of `A` is .###.
+    let (x, y) = dot(0, 0);
+    assert_eq!(image.pixel(x, y), Theme::GREEN.pixel_off);
+    let (x, y) = dot(1, 0);
+    assert_eq!(image.pixel(x, y), Theme::GREEN.pixel_on);
+    assert_eq!(image.pixel(x + 3, y), Theme::GREEN.background);
+}
+
+#[test]
+fn render_should_use_selected_rom_and_theme() {
+    let mut lcd = display(FunctionMode::LINES_2, 1, 1);
+    lcd.write_all(b"\\").unwrap();
+    let mut renderer = Renderer::new(CharRom::A02);
+    renderer.set_theme(Theme::AMBER);
+    // Backslash has nothing in the top row while the A00 yen sign does.
+    let (x, y) = dot(0, 0);
+    assert_eq!(renderer.render(&lcd).pixel(x, y), Theme::AMBER.pixel_off);
+    let renderer = Renderer::new(CharRom::A00);
+    assert_eq!(renderer.render(&lcd).pixel(x, y), Theme::GREEN.pixel_on);
+}
+
+#[test]
+fn render_should_honor_5x10_font_mode() {
+    let mut lcd = display(FunctionMode::DOTS_5X10, 2, 1);
+    lcd.set_cg_ram_addr(16).unwrap();
+    let mut pattern = [0u8; 16];
+    pattern[9] = 0x1F;
+    lcd.write_all(&pattern).unwrap();
+    lcd.set_dd_ram_addr(0).unwrap();
+    // Custom character 1 is shared by codes 2 and 3, 0xE7 is `g` with descender.
+    lcd.write_all(&[0x03, 0xE7]).unwrap();
+    let image = Renderer::new(CharRom::A00).render(&lcd);
+    assert_eq!(image.height(), 16 + 11 * 4);
+    let (x, y) = dot(0, 9);
+    assert_eq!(image.pixel(x, y), Theme::GREEN.pixel_on);
+    let (x, y) = dot(6 + 1, 9);
+    assert_eq!(image.pixel(x, y), Theme::GREEN.pixel_on);
+}
+
+#[test]
+fn image_should_encode_ppm_and_png() {
+    let lcd = display(FunctionMode::LINES_2, 1, 1);
+    let image = Renderer::new(CharRom::A00).render(&lcd);
+    let ppm = image.to_ppm();
+    assert!(ppm.starts_with(b"P6\n36 48\n255\n"));
+    assert_eq!(ppm.len(), 13 + 36 * 48 * 3);
+    let png = image.to_png();
+    assert_eq!(png[..8], [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+    assert_eq!(png[12..16], *b"IHDR");
+    assert_eq!(
+        png[png.len() - 12..],
+        [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]
+    );
+}
+
+#[test]
+fn a02_should_have_glyphs_for_0x10_to_0x1f_and_0x80_to_0x9f() {
+    let mut lcd = display(FunctionMode::LINES_2, 2, 1);
+    lcd.write_all(&[0x10, 0x80]).unwrap();
+    // Both `▶` and `Б` have the left dot of the top row on.
+    let (x, y) = dot(0, 0);
+    let (x2, _) = dot(6, 0);
+    let image = Renderer::new(CharRom::A02).render(&lcd);
+    assert_eq!(image.pixel(x, y), Theme::GREEN.pixel_on);
+    assert_eq!(image.pixel(x2, y), Theme::GREEN.pixel_on);
+    let image = Renderer::new(CharRom::A00).render(&lcd);
+    assert_eq!(image.pixel(x, y), Theme::GREEN.pixel_off);
+    assert_eq!(image.pixel(x2, y), Theme::GREEN.pixel_off);
+    assert!((0x10..=0x1F)
+        .chain(0x80..=0x9F)
+        .all(|code| CharRom::A02.glyph(code) != [0; 10]));
+}
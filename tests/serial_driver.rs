@@ -0,0 +1,69 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{DisplayMode, EntryMode, SerialDriver, SerialProtocol, ShiftMode, HD44780};
+use std::io::Write;
+
+#[test]
+fn serlcd_init_should_pass_commands_through_without_function_set() {
+    let mut sut = SerialDriver::new(Vec::new(), SerialProtocol::SerLcd);
+    sut.init(None, DisplayMode::DISPLAY_ON, None).unwrap();
+    assert_eq!(sut.get_ref(), &vec![0xFE, 0x0C, 0xFE, 0x06, 0xFE, 0x01]);
+}
+
+#[test]
+fn serlcd_should_upload_custom_characters_and_display_them() {
+    let mut sut = SerialDriver::new(Vec::new(), SerialProtocol::SerLcd);
+    sut.set_cg_ram_addr(8).unwrap();
+    sut.write_all(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+    sut.set_dd_ram_addr(0x40).unwrap();
+    sut.write_all(&[0x01, b'A', b'|', 0xFE]).unwrap();
+    assert_eq!(
+        sut.get_ref(),
+        &vec![0x7C, 28, 1, 2, 3, 4, 5, 6, 7, 8, 0xFE, 0xC0, 0x7C, 36, b'A', b' ', b' ']
+    );
+}
+
+#[test]
+fn matrix_orbital_should_translate_commands() {
+    let mut sut = SerialDriver::new(Vec::new(), SerialProtocol::MatrixOrbital { columns: 20 });
+    sut.set_dd_ram_addr(0x54 + 3).unwrap();
+    sut.cursor_shift(ShiftMode::MOVE_RIGHT).unwrap();
+    sut.display_control(DisplayMode::DISPLAY_ON | DisplayMode::CURSOR_ON)
+        .unwrap();
+    sut.clear_display().unwrap();
+    sut.write_all(&[b'a', 0xFE, b'X']).unwrap();
+    assert_eq!(
+        sut.get_ref(),
+        &vec![
+            0xFE, b'G', 4, 4, 0xFE, b'M', 0xFE, b'B', 0, 0xFE, b'J', 0xFE, b'T', 0xFE, b'X', b'a',
+            b' ', b'X'
+        ]
+    );
+}
+
+#[test]
+fn matrix_orbital_should_reject_display_shift_and_entry_modes() {
+    let mut sut = SerialDriver::new(Vec::new(), SerialProtocol::MatrixOrbital { columns: 16 });
+    assert!(sut.cursor_shift(ShiftMode::DISPLAY_MOVE).is_err());
+    assert!(sut.entry_mode_set(EntryMode::empty()).is_err());
+}
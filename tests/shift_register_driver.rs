@@ -0,0 +1,84 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use embedded_hal::blocking::spi;
+use embedded_hal::digital::v2::OutputPin;
+use hd44780_ntb::{FunctionMode, ShiftRegisterDriver, VirtualClock, HD44780};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// Last byte shifted in and every byte that was latched onto the outputs.
+#[derive(Default)]
+struct Register {
+    shifted: u8,
+    latched: Vec<u8>,
+}
+
+#[derive(Clone, Default)]
+struct MockSpi(Rc<RefCell<Register>>);
+
+impl spi::Write<u8> for MockSpi {
+    type Error = ();
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        for word in words {
+            self.0.borrow_mut().shifted = *word;
+        }
+        Ok(())
+    }
+}
+
+struct MockLatch(Rc<RefCell<Register>>);
+
+impl OutputPin for MockLatch {
+    type Error = ();
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        let mut register = self.0.borrow_mut();
+        let byte = register.shifted;
+        register.latched.push(byte);
+        Ok(())
+    }
+}
+
+#[test]
+fn write_should_latch_three_frames_per_nibble() {
+    let spi = MockSpi::default();
+    let latch = MockLatch(spi.0.clone());
+    let mut sut = ShiftRegisterDriver::new(spi.clone(), latch, VirtualClock::new());
+    sut.write_all(b"A").unwrap();
+    // 'A' = 0x41 with D4-D7 on Q6-Q3, RS on Q1, E on Q2, and backlight on Q7.
+    assert_eq!(
+        spi.0.borrow().latched,
+        vec![0x92, 0x96, 0x92, 0xC2, 0xC6, 0xC2]
+    );
+}
+
+#[test]
+fn init_should_return_error_when_8_bit_mode_requested() {
+    let spi = MockSpi::default();
+    let latch = MockLatch(spi.0.clone());
+    let mut sut = ShiftRegisterDriver::new(spi, latch, VirtualClock::new());
+    assert!(sut.init(FunctionMode::BITS_8, None, None).is_err());
+}
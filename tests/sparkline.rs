@@ -0,0 +1,125 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod common;
+
+use hd44780_ntb::{FunctionMode, Geometry, HdError, Sparkline};
+use std::io::Write;
+
+#[test]
+fn draw_should_scale_values_to_bar_heights() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut chart = Sparkline::new(0, 0, 8);
+    chart.load(&mut sut).unwrap();
+    for value in &[10, 20, 30, 80] {
+        chart.push(*value);
+    }
+    chart.draw(&mut sut).unwrap();
+    assert_eq!(
+        sut.get_ref().glyphs()[0][..8],
+        [b' ', b' ', b' ', b' ', 0, 1, 2, 7]
+    );
+    assert_eq!(sut.get_ref().custom_char(0), [0, 0, 0, 0, 0, 0, 0, 0x1F]);
+    assert_eq!(sut.get_ref().custom_char(7), [0x1F; 8]);
+}
+
+#[test]
+fn draw_should_stack_tall_charts_and_show_labels() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut chart = Sparkline::new(4, 0, 6);
+    chart.set_tall(true);
+    chart.set_label_width(2);
+    for value in &[-5, 0, 5, 10] {
+        chart.push(*value);
+    }
+    chart.draw(&mut sut).unwrap();
+    let glyphs = sut.get_ref().glyphs();
+    assert_eq!(glyphs[0][4..10], [b'1', b'0', b' ', b' ', 2, 7]);
+    assert_eq!(glyphs[1][4..10], [b'-', b'5', 0, 5, 7, 7]);
+    let mut chart = Sparkline::new(0, 1, 4);
+    chart.set_tall(true);
+    assert!(matches!(
+        chart.draw(&mut sut),
+        Err(HdError::OffScreen(3, 2))
+    ));
+}
+
+#[test]
+fn push_should_drop_oldest_values_past_chart_width() {
+    let mut chart = Sparkline::new(0, 0, 7);
+    chart.set_label_width(2);
+    assert_eq!(chart.chart_width(), 3);
+    for value in 1..=5 {
+        chart.push(value);
+    }
+    assert_eq!(
+        chart.values().iter().copied().collect::<Vec<_>>(),
+        [3, 4, 5]
+    );
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    chart.push(5);
+    chart.draw(&mut sut).unwrap();
+    assert_eq!(
+        sut.get_ref().glyphs()[0][..7],
+        [b' ', b'4', 0, 7, 7, b' ', b'5']
+    );
+}
+
+#[test]
+fn set_tall_false_should_trim_values_and_mark_wide_labels() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    let mut chart = Sparkline::new(0, 0, 8);
+    chart.set_label_width(2);
+    chart.set_tall(true);
+    for value in 95..=100 {
+        chart.push(value);
+    }
+    chart.set_tall(false);
+    assert_eq!(chart.values().len(), 4);
+    chart.draw(&mut sut).unwrap();
+    assert_eq!(
+        sut.get_ref().glyphs()[0][..8],
+        [b'9', b'7', 0, 2, 5, 7, b'#', b'#']
+    );
+}
+
+#[test]
+fn draw_should_narrow_labels_to_fit_widget() {
+    let mut sut = common::emulator(Geometry::LCD_16X2, FunctionMode::LINES_2);
+    sut.write_all(&[b'x'; 16]).unwrap();
+    let mut chart = Sparkline::new(0, 0, 5);
+    chart.set_label_width(4);
+    assert_eq!(chart.chart_width(), 1);
+    chart.push(1);
+    chart.push(50);
+    chart.draw(&mut sut).unwrap();
+    assert_eq!(
+        sut.get_ref().glyphs()[0][..6],
+        [b'5', b'0', 0, b'5', b'0', b'x']
+    );
+    chart.set_tall(true);
+    chart.set_label_width(9);
+    assert_eq!(chart.chart_width(), 0);
+    assert!(chart.values().is_empty());
+    chart.draw(&mut sut).unwrap();
+    assert_eq!(sut.get_ref().glyphs()[0][..6], *b"    0x");
+}
@@ -0,0 +1,75 @@
+// MIT License
+//
+// Copyright © 2020-present, Michael Cummings <mgcummings@yahoo.com>.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use hd44780_ntb::{DisplayMode, FunctionMode, Geometry, Key, Lcd, TerminalDriver, HD44780};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+fn last_frame(sut: &TerminalDriver<Vec<u8>>) -> String {
+    let out = String::from_utf8_lossy(sut.get_ref()).into_owned();
+    let start = out.rfind("\x1b[H").unwrap();
+    out[start + 3..].to_string()
+}
+
+#[test]
+fn should_redraw_bordered_window_after_each_write() {
+    let mut sut = TerminalDriver::new(Vec::new(), 4, 2);
+    sut.init(FunctionMode::LINES_2, DisplayMode::DISPLAY_ON, None)
+        .unwrap();
+    write!(sut, "Hi").unwrap();
+    assert_eq!(last_frame(&sut), "+----+\r\n|Hi  |\r\n|    |\r\n+----+\r\n");
+}
+
+#[test]
+fn should_show_cursor_and_custom_characters() {
+    let mut sut = TerminalDriver::new(Vec::new(), 2, 1);
+    let dc = DisplayMode::DISPLAY_ON | DisplayMode::CURSOR_ON;
+    sut.init(FunctionMode::LINES_2, dc, None).unwrap();
+    sut.set_cg_ram_addr(0).unwrap();
+    sut.write_all(&[0x1F; 8]).unwrap();
+    sut.set_dd_ram_addr(0).unwrap();
+    sut.write_all(&[0x00]).unwrap();
+    assert_eq!(last_frame(&sut), "+--+\r\n|█\x1b[4m \x1b[0m|\r\n+--+\r\n");
+}
+
+#[test]
+fn input_should_pass_decoded_keys_to_handler() {
+    let keys = Rc::new(RefCell::new(Vec::new()));
+    let mut sut = TerminalDriver::new(Vec::new(), 16, 2);
+    let log = keys.clone();
+    sut.on_key(move |key| log.borrow_mut().push(key));
+    sut.input(b"\x1b[A\x1bOBx\r\x1b");
+    assert_eq!(
+        *keys.borrow(),
+        vec![Key::Up, Key::Down, Key::Char('x'), Key::Enter, Key::Escape]
+    );
+}
+
+#[test]
+fn debug_should_show_if_key_handler_is_set() {
+    let mut sut = TerminalDriver::new(Vec::new(), 16, 2);
+    sut.on_key(|_| {});
+    assert!(format!("{:?}", sut).contains("key_handler: true"));
+    let lcd = Lcd::new(sut, Geometry::LCD_16X2);
+    assert!(format!("{:?}", lcd).starts_with("Lcd"));
+}